@@ -0,0 +1,37 @@
+//cargo bench
+//times factorization and reduction of large intervals, which used to take minutes with trial division
+
+use std::{hint::black_box, time::Instant};
+use interval_info::*;
+
+fn bench<T>(name: &str, runs: u32, mut f: impl FnMut() -> T) {
+	let start = Instant::now();
+	for _ in 0..runs {
+		black_box(f());
+	}
+	println!("{:<45}: {:?} per run", name, start.elapsed() / runs);
+}
+
+fn main() {
+	let intervals = [
+		("monzisma", Interval::new(450359962737049600, 450283905890997363)),
+		("'41-tone' comma", Interval::new(36893488147419103232, 36472996377170786403)),
+		("Mercator's comma", Interval::new(19383245667680019896796723, 19342813113834066795298816)),
+		("two 64 bit primes", Interval::new(18446744073709551557 * 3, 18446744073709551533 * 2)),
+		("unreduced", Interval::new(18446744073709551557 * 4294967291, 18446744073709551557 * 4294967279)),
+	];
+	
+	for (name, i) in intervals {
+		bench(&format!("{} reduce", name), 10000, || i.reduced());
+		bench(&format!("{} largest_prime_factor", name), 100, || {
+			largest_prime_factor(i.num).max(largest_prime_factor(i.den))
+		});
+		bench(&format!("{} PrimeFactors::init", name), 100, || {
+			(PrimeFactors::init(i.num), PrimeFactors::init(i.den))
+		});
+	}
+	
+	bench("is_prime 2^127 - 1", 100, || is_prime((1 << 127) - 1));
+	bench("factorize 2^128 - 1", 10, || factorize(u128::MAX));
+	bench("factorize 4294967291 * 18446744073709551557", 10, || factorize(4294967291 * 18446744073709551557));
+}
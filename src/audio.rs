@@ -0,0 +1,188 @@
+//synthesizing intervals to mono 16 bit WAV files, for comparing them by ear
+
+use std::{f64::consts::TAU, fmt, fs, io, path::Path, str::FromStr};
+
+pub const SAMPLE_RATE: u32 = 44100;
+//fade in and out of every tone, in seconds, so they do not click
+const FADE: f64 = 0.01;
+//peak amplitude of a mix, leaving some headroom
+const VOLUME: f64 = 0.8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Timbre {
+	Sine,
+	Saw,
+	//the first 16 harmonics, falling off slower than a saw
+	Harmonic
+}
+
+impl Timbre {
+	//harmonic number and amplitude of each partial
+	pub fn partials(&self) -> Vec<(f64, f64)> {
+		match self {
+			Timbre::Sine => vec![(1.0, 1.0)],
+			Timbre::Saw => (1..=64).map(|k| (k as f64, 1.0 / k as f64)).collect(),
+			Timbre::Harmonic => (1..=16).map(|k| (k as f64, 1.0 / (k as f64).sqrt())).collect(),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RenderParseError {
+	Timbre(String),
+	Arrangement(String)
+}
+
+impl fmt::Display for RenderParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			RenderParseError::Timbre(s) => write!(f, "unknown timbre {}, use sine, saw or harmonic", s),
+			RenderParseError::Arrangement(s) => write!(f, "unknown arrangement {}, use dyad, arpeggio or both", s),
+		}
+	}
+}
+
+impl std::error::Error for RenderParseError {}
+
+impl FromStr for Timbre {
+	type Err = RenderParseError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim() {
+			"sine" => Ok(Timbre::Sine),
+			"saw" => Ok(Timbre::Saw),
+			"harmonic" => Ok(Timbre::Harmonic),
+			_ => Err(RenderParseError::Timbre(s.to_string())),
+		}
+	}
+}
+
+//mono samples between -1.0 and 1.0
+pub struct Wav {
+	pub sample_rate: u32,
+	pub samples: Vec<f64>
+}
+
+impl Wav {
+	pub fn new(sample_rate: u32) -> Self {
+		Self{sample_rate, samples: Vec::new()}
+	}
+	
+	fn sample_count(&self, seconds: f64) -> usize {
+		(seconds * self.sample_rate as f64).round() as usize
+	}
+	
+	pub fn push_silence(&mut self, seconds: f64) {
+		let count = self.sample_count(seconds);
+		self.samples.resize(self.samples.len() + count, 0.0);
+	}
+	
+	//all of the frequencies at once, partials above the nyquist frequency are left out so they do not alias
+	pub fn push_tones(&mut self, frequencies: &[f64], seconds: f64, timbre: Timbre) {
+		let count = self.sample_count(seconds);
+		if count == 0 {
+			return;
+		}
+		let rate = self.sample_rate as f64;
+		let nyquist = rate / 2.0;
+		let partials = timbre.partials();
+		let total: f64 = partials.iter().map(|(_, a)| a).sum::<f64>() * frequencies.len().max(1) as f64;
+		let fade = self.sample_count(FADE).max(1).min(count / 2 + 1);
+		
+		for n in 0..count {
+			let t = n as f64 / rate;
+			let mut value = 0.0;
+			for f in frequencies {
+				for (k, amplitude) in &partials {
+					if f * k < nyquist {
+						value += amplitude * (TAU * f * k * t).sin();
+					}
+				}
+			}
+			let envelope = (n.min(count - 1 - n) as f64 / fade as f64).min(1.0);
+			self.samples.push(value / total * VOLUME * envelope);
+		}
+	}
+	
+	//the notes one after another
+	pub fn push_arpeggio(&mut self, frequencies: &[f64], seconds: f64, timbre: Timbre) {
+		for f in frequencies {
+			self.push_tones(&[*f], seconds, timbre);
+		}
+	}
+	
+	pub fn seconds(&self) -> f64 {
+		self.samples.len() as f64 / self.sample_rate as f64
+	}
+	
+	//16 bit PCM
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let data_len = self.samples.len() as u32 * 2;
+		let mut bytes = Vec::with_capacity(44 + data_len as usize);
+		bytes.extend(b"RIFF");
+		bytes.extend((36 + data_len).to_le_bytes());
+		bytes.extend(b"WAVE");
+		
+		bytes.extend(b"fmt ");
+		bytes.extend(16u32.to_le_bytes());
+		//PCM, mono
+		bytes.extend(1u16.to_le_bytes());
+		bytes.extend(1u16.to_le_bytes());
+		bytes.extend(self.sample_rate.to_le_bytes());
+		//bytes per second, bytes per sample, bits per sample
+		bytes.extend((self.sample_rate * 2).to_le_bytes());
+		bytes.extend(2u16.to_le_bytes());
+		bytes.extend(16u16.to_le_bytes());
+		
+		bytes.extend(b"data");
+		bytes.extend(data_len.to_le_bytes());
+		for s in &self.samples {
+			let s = (s.clamp(-1.0, 1.0) * i16::MAX as f64).round() as i16;
+			bytes.extend(s.to_le_bytes());
+		}
+		bytes
+	}
+	
+	pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		fs::write(path, self.to_bytes())
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Arrangement {
+	Dyad,
+	Arpeggio,
+	//arpeggio, then dyad
+	Both
+}
+
+impl FromStr for Arrangement {
+	type Err = RenderParseError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim() {
+			"dyad" => Ok(Arrangement::Dyad),
+			"arpeggio" => Ok(Arrangement::Arpeggio),
+			"both" => Ok(Arrangement::Both),
+			_ => Err(RenderParseError::Arrangement(s.to_string())),
+		}
+	}
+}
+
+//the just interval above base, then the approximated one, with a pause between them
+pub fn render_comparison(base: f64, cents: f64, approximation: f64, timbre: Timbre, arrangement: Arrangement) -> Wav {
+	let mut wav = Wav::new(SAMPLE_RATE);
+	for (n, c) in [cents, approximation].iter().enumerate() {
+		if n > 0 {
+			wav.push_silence(1.0);
+		}
+		let frequencies = [base, base * (c / 1200.0).exp2()];
+		if arrangement != Arrangement::Dyad {
+			wav.push_arpeggio(&frequencies, 1.0, timbre);
+		}
+		if arrangement != Arrangement::Arpeggio {
+			wav.push_tones(&frequencies, 2.0, timbre);
+		}
+	}
+	wav
+}
@@ -0,0 +1,84 @@
+//beating and periodicity of an interval played at a reference frequency
+
+use crate::{dissonance, wide_mul, ApproximationRow, Frequency, Pitch};
+
+//highest partial of either note checked for near coincidences
+pub const BEAT_PARTIALS: u32 = 16;
+//partials further apart than this are heard as two notes rather than as beating
+pub const BEAT_LIMIT: f64 = 30.0;
+
+pub struct BeatReport {
+	pub base: Frequency,
+	pub upper: Frequency,
+	//seconds until the combined waveform repeats, only for ratios
+	pub period: Option<f64>,
+	//Sethares dissonance of both notes with the partials given
+	pub dissonance: f64,
+	pub approximations: Vec<ApproximationBeat>,
+	//pairs of partials less than BEAT_LIMIT Hz apart, including ones that coincide exactly
+	pub partials: Vec<PartialBeat>
+}
+
+pub struct ApproximationBeat {
+	pub scale: String,
+	//frequency of the approximated upper note
+	pub frequency: Frequency,
+	//Hz, between the partials that coincide for the just ratio, only for ratios
+	pub beat: Option<f64>
+}
+
+pub struct PartialBeat {
+	//harmonic number of the partial of each note, 1 is the fundamental
+	pub lower: u32,
+	pub upper: u32,
+	pub lower_frequency: f64,
+	pub upper_frequency: f64,
+	//Hz, 0 if they coincide
+	pub beat: f64
+}
+
+impl BeatReport {
+	//timbre is the (harmonic number, amplitude) of each partial of both notes, for the dissonance
+	pub fn new(base: Frequency, pitch: &Pitch, approximations: &[&ApproximationRow], timbre: &[(f64, f64)]) -> Self {
+		let upper = base.above(pitch.cents());
+		
+		//the ratio repeats every den periods of the lower note, which is num periods of the upper note
+		let ratio = match pitch {
+			Pitch::Ratio(i) => Some(i.reduced()),
+			Pitch::Cents(_) => None,
+		};
+		let period = ratio.map(|i| i.den as f64 / base.0);
+		
+		let approximations = approximations.iter().filter_map(|row| {
+			let offset = row.approximation.as_ref().ok()?.offset;
+			let frequency = base.above(pitch.cents() + offset);
+			//partial den of the upper note against partial num of the lower note, which coincide for the just ratio
+			let beat = ratio.map(|i| (i.den as f64 * frequency.0 - i.num as f64 * base.0).abs());
+			Some(ApproximationBeat{scale: row.scale.clone(), frequency, beat})
+		}).collect();
+		
+		let mut partials = Vec::new();
+		for lower in 1..=BEAT_PARTIALS {
+			for upper_partial in 1..=BEAT_PARTIALS {
+				let lower_frequency = base.0 * lower as f64;
+				let upper_frequency = upper.0 * upper_partial as f64;
+				let mut beat = (lower_frequency - upper_frequency).abs();
+				if ratio.is_some_and(|i| wide_mul(i.num, upper_partial as u128) == wide_mul(i.den, lower as u128)) {
+					beat = 0.0;
+				}
+				if beat < BEAT_LIMIT {
+					partials.push(PartialBeat{lower, upper: upper_partial, lower_frequency, upper_frequency, beat});
+				}
+			}
+		}
+		
+		let dissonance = dissonance(base.0, pitch.cents(), timbre);
+		
+		Self{base, upper, period, dissonance, approximations, partials}
+	}
+	
+	//frequency the combined waveform repeats at, only for ratios
+	pub fn periodicity(&self) -> Option<Frequency> {
+		self.period.map(|p| Frequency(1.0 / p))
+	}
+}
@@ -0,0 +1,263 @@
+//chords of just intervals, written as harmonics like 4:5:6:7 or as ratios above the root like 5/4,3/2
+
+use std::{fmt, str::FromStr};
+use crate::{closest_edo_note, gcd, largest_prime_factor, parse_u128, Interval, IntervalParseError, Json, Pitch, ScaleError, ToJson};
+
+//lowest harmonics of the chord, ascending and without common factors
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Chord {
+	pub harmonics: Vec<u128>
+}
+
+pub struct Dyad {
+	//harmonics of the two notes
+	pub lower: u128,
+	pub upper: u128,
+	pub interval: Interval
+}
+
+pub struct ChordEdo {
+	pub div: u64,
+	//steps of each note above the root, in the same order as the harmonics
+	pub notes: Vec<i64>,
+	//largest error of any dyad, in cents
+	pub max_error: f64
+}
+
+impl Chord {
+	//sorts, removes repeated notes and divides out common factors
+	pub fn new(mut harmonics: Vec<u128>) -> Self {
+		harmonics.sort();
+		harmonics.dedup();
+		let common = harmonics.iter().fold(0, |a, b| gcd(a, *b));
+		if common > 1 {
+			harmonics.iter_mut().for_each(|h| *h /= common);
+		}
+		Self{harmonics}
+	}
+	
+	//ratios above the root, the root 1/1 is always included
+	//None if the harmonics are too large for u128
+	pub fn from_ratios(ratios: &[Interval]) -> Option<Self> {
+		let ratios: Vec<Interval> = ratios.iter().map(|r| r.reduced()).collect();
+		let den = ratios.iter().try_fold(1, |l, r| lcm(l, r.den))?;
+		let mut harmonics = vec![den];
+		for r in ratios {
+			harmonics.push(r.num.checked_mul(den / r.den)?);
+		}
+		Some(Self::new(harmonics))
+	}
+	
+	//every pair of notes, lower note first
+	pub fn dyads(&self) -> Vec<Dyad> {
+		let mut dyads = Vec::new();
+		for (n, lower) in self.harmonics.iter().enumerate() {
+			for upper in &self.harmonics[n + 1..] {
+				dyads.push(Dyad{lower: *lower, upper: *upper, interval: Interval::new(*upper, *lower).reduced()});
+			}
+		}
+		dyads
+	}
+	
+	//the same chord as undertones, e.g. 4:5:6 is 1/(15:12:10), listed from the lowest note
+	//None if the undertones are too large for u128
+	pub fn utonal(&self) -> Option<Vec<u128>> {
+		let l = self.harmonics.iter().try_fold(1, |l, h| lcm(l, *h))?;
+		Some(self.harmonics.iter().map(|h| l / h).collect())
+	}
+	
+	//largest prime factor of any note
+	pub fn prime_limit(&self) -> u128 {
+		self.harmonics.iter().map(|h| largest_prime_factor(*h)).max().unwrap_or(1)
+	}
+	
+	//true if the chord is written with smaller numbers as undertones than as harmonics
+	pub fn is_utonal(&self) -> bool {
+		match self.utonal() {
+			Some(u) => u.iter().max() < self.harmonics.iter().max(),
+			None => false,
+		}
+	}
+	
+	//cents of each note above the root
+	pub fn cents(&self) -> Vec<f64> {
+		let root = self.harmonics.first().copied().unwrap_or(1);
+		self.harmonics.iter().map(|h| Interval::new(*h, root).cents()).collect()
+	}
+	
+	//EDOs where every dyad is within tolerance cents, each note mapped to its closest step above the root
+	//best first, by the largest error of any dyad
+	pub fn best_edos(&self, min_div: u64, max_div: u64, tolerance: f64) -> Result<Vec<ChordEdo>, ScaleError> {
+		let cents = self.cents();
+		let mut edos = Vec::new();
+		for div in min_div.max(1)..=max_div {
+			let notes = cents.iter()
+				.map(|c| closest_edo_note(*c, div, true).map(|a| a.note))
+				.collect::<Result<Vec<i64>, ScaleError>>()?;
+			
+			let step = 1200.0 / div as f64;
+			let mut max_error: f64 = 0.0;
+			for n in 0..notes.len() {
+				for m in n + 1..notes.len() {
+					let error = (notes[m] - notes[n]) as f64 * step - (cents[m] - cents[n]);
+					max_error = max_error.max(error.abs());
+				}
+			}
+			
+			if max_error <= tolerance {
+				edos.push(ChordEdo{div, notes, max_error});
+			}
+		}
+		edos.sort_by(|a, b| a.max_error.total_cmp(&b.max_error).then(a.div.cmp(&b.div)));
+		Ok(edos)
+	}
+	
+	//true if the input is written like a chord rather than a single interval
+	pub fn is_chord(s: &str) -> bool {
+		s.contains(':') || split_list(s).len() > 1
+	}
+}
+
+fn lcm(a: u128, b: u128) -> Option<u128> {
+	(a / gcd(a, b)).checked_mul(b)
+}
+
+//splits on commas that are not inside of a monzo
+fn split_list(s: &str) -> Vec<&str> {
+	let mut parts = Vec::new();
+	let mut depth = 0;
+	let mut start = 0;
+	for (n, c) in s.char_indices() {
+		match c {
+			'[' | '|' if depth == 0 => depth = 1,
+			'>' | '⟩' | ']' => depth = 0,
+			',' if depth == 0 => {
+				parts.push(&s[start..n]);
+				start = n + 1;
+			}
+			_ => {}
+		}
+	}
+	parts.push(&s[start..]);
+	parts
+}
+
+impl fmt::Display for Chord {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let harmonics: Vec<String> = self.harmonics.iter().map(|h| h.to_string()).collect();
+		write!(f, "{}", harmonics.join(":"))
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChordParseError {
+	TooFewNotes,
+	//only ratios can be turned into harmonics
+	NotRatio(String),
+	Overflow,
+	Interval(IntervalParseError)
+}
+
+impl fmt::Display for ChordParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ChordParseError::TooFewNotes => write!(f, "a chord needs at least two different notes"),
+			ChordParseError::NotRatio(s) => write!(f, "chord notes have to be ratios: {}", s),
+			ChordParseError::Overflow => write!(f, "chord harmonics too large, max is {}", u128::MAX),
+			ChordParseError::Interval(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl std::error::Error for ChordParseError {}
+
+impl From<IntervalParseError> for ChordParseError {
+	fn from(e: IntervalParseError) -> Self {
+		ChordParseError::Interval(e)
+	}
+}
+
+//accepts
+//4:5:6:7       harmonics
+//5/4,3/2       ratios above the root, in any of the pitch formats that are exact
+impl FromStr for Chord {
+	type Err = ChordParseError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let chord = if s.contains(':') {
+			let harmonics = s.split(':').map(|h| match parse_u128(h.trim())? {
+				0 => Err(IntervalParseError::Zero),
+				h => Ok(h),
+			}).collect::<Result<Vec<u128>, IntervalParseError>>()?;
+			Chord::new(harmonics)
+		} else {
+			let mut ratios = Vec::new();
+			for r in split_list(s) {
+				match r.parse::<Pitch>()? {
+					Pitch::Ratio(i) => ratios.push(i),
+					Pitch::Cents(_) => return Err(ChordParseError::NotRatio(r.trim().to_string())),
+				}
+			}
+			Chord::from_ratios(&ratios).ok_or(ChordParseError::Overflow)?
+		};
+		
+		if chord.harmonics.len() < 2 {
+			return Err(ChordParseError::TooFewNotes);
+		}
+		Ok(chord)
+	}
+}
+
+//everything shown about a chord
+pub struct ChordReport {
+	pub input: String,
+	pub chord: Chord,
+	pub utonal: Option<Vec<u128>>,
+	pub dyads: Vec<Dyad>,
+	//EDOs within the tolerance, best first
+	pub edos: Vec<ChordEdo>,
+	pub tolerance: f64
+}
+
+impl ChordReport {
+	pub fn new(input: &str, chord: Chord, min_div: u64, max_div: u64, tolerance: f64) -> Result<Self, ScaleError> {
+		Ok(Self{
+			input: input.to_string(),
+			utonal: chord.utonal(),
+			dyads: chord.dyads(),
+			edos: chord.best_edos(min_div, max_div, tolerance)?,
+			chord,
+			tolerance
+		})
+	}
+}
+
+impl ToJson for ChordReport {
+	fn to_json(&self) -> Json {
+		let harmonics: Vec<u128> = self.chord.harmonics.clone();
+		Json::object()
+			.with("input", self.input.as_str())
+			.with("chord", self.chord.to_string())
+			.with("harmonics", harmonics)
+			.with("undertones", self.utonal.clone())
+			.with("utonal", self.chord.is_utonal())
+			.with("prime_limit", self.chord.prime_limit())
+			.with("cents", self.chord.cents())
+			.with("dyads", Json::Array(self.dyads.iter().map(|d| {
+				Json::object()
+					.with("lower", d.lower)
+					.with("upper", d.upper)
+					.with("interval", d.interval.to_string())
+					.with("cents", d.interval.cents())
+					.with("name", d.interval.get_name())
+			}).collect()))
+			.with("tolerance", self.tolerance)
+			.with("edos", Json::Array(self.edos.iter().map(|e| {
+				Json::object()
+					.with("div", e.div)
+					.with("notes", e.notes.clone())
+					.with("max_error", e.max_error)
+			}).collect()))
+	}
+}
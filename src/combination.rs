@@ -0,0 +1,93 @@
+//combination tones heard when two notes sound together, and the fundamental they imply
+//for n/d in lowest terms the notes are harmonics d and n of a virtual fundamental 1/d of the lower note
+
+use crate::{Interval, Scale, ScaleApproximation, ScaleError};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CombinationKind {
+	Difference,
+	Summation
+}
+
+impl CombinationKind {
+	pub fn name(&self) -> &'static str {
+		match self {
+			CombinationKind::Difference => "difference",
+			CombinationKind::Summation => "summation",
+		}
+	}
+}
+
+//the tone at |lower * f1 + upper * f2|, where f1 is the lower note and f2 the upper one
+pub struct CombinationTone {
+	pub kind: CombinationKind,
+	//1 for f2-f1 and f1+f2, 2 for 2f1-f2 and the like
+	pub order: u8,
+	pub lower: i8,
+	pub upper: i8,
+	//relative to the lower note
+	pub interval: Interval,
+	pub edo_12: Result<ScaleApproximation, ScaleError>
+}
+
+impl CombinationTone {
+	//e.g. 2f1-f2
+	pub fn formula(&self) -> String {
+		let term = |c: i8, f: &str| match c.abs() {
+			1 => f.to_string(),
+			c => format!("{}{}", c, f),
+		};
+		if self.lower < 0 {
+			format!("{}-{}", term(self.upper, "f2"), term(self.lower, "f1"))
+		} else if self.upper < 0 {
+			format!("{}-{}", term(self.lower, "f1"), term(self.upper, "f2"))
+		} else {
+			format!("{}+{}", term(self.lower, "f1"), term(self.upper, "f2"))
+		}
+	}
+}
+
+//coefficients of f1 and f2 of the first and second order tones
+const COMBINATIONS: [(CombinationKind, u8, i8, i8); 6] = [
+	(CombinationKind::Difference, 1, -1, 1),
+	(CombinationKind::Difference, 2, 2, -1),
+	(CombinationKind::Difference, 2, -1, 2),
+	(CombinationKind::Summation, 1, 1, 1),
+	(CombinationKind::Summation, 2, 2, 1),
+	(CombinationKind::Summation, 2, 1, 2),
+];
+
+pub struct CombinationReport {
+	//relative to the lower note
+	pub virtual_fundamental: Interval,
+	pub fundamental_edo_12: Result<ScaleApproximation, ScaleError>,
+	//tones that would be silent or are too large for u128 are left out
+	pub tones: Vec<CombinationTone>
+}
+
+impl CombinationReport {
+	pub fn new(interval: Interval, scale_start_0: bool) -> Self {
+		let i = interval.reduced();
+		let (f1, f2) = (i.num.min(i.den), i.num.max(i.den));
+		let edo_12 = Scale::edo(12);
+		
+		let tones = COMBINATIONS.iter().filter_map(|(kind, order, lower, upper)| {
+			let a = f1.checked_mul(lower.unsigned_abs() as u128)?;
+			let b = f2.checked_mul(upper.unsigned_abs() as u128)?;
+			let num = match (*lower < 0, *upper < 0) {
+				(false, false) => a.checked_add(b)?,
+				_ => a.abs_diff(b),
+			};
+			if num == 0 {
+				return None;
+			}
+			let interval = Interval::new(num, f1).reduced();
+			let edo_12 = edo_12.closest_note(interval.cents(), scale_start_0);
+			Some(CombinationTone{kind: *kind, order: *order, lower: *lower, upper: *upper, interval, edo_12})
+		}).collect();
+		
+		let virtual_fundamental = Interval::new(1, f1);
+		let fundamental_edo_12 = edo_12.closest_note(virtual_fundamental.cents(), scale_start_0);
+		Self{virtual_fundamental, fundamental_edo_12, tones}
+	}
+}
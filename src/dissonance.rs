@@ -0,0 +1,113 @@
+//sensory dissonance, the roughness of beating partials, using Sethares' fit of the Plomp-Levelt curve
+
+use crate::{Interval, NAMES};
+
+//constants of Sethares' model, the curve peaks at about a quarter of a critical band
+const X_STAR: f64 = 0.24;
+const S1: f64 = 0.0207;
+const S2: f64 = 18.96;
+const B1: f64 = 3.51;
+const B2: f64 = 5.75;
+
+//roughness of two sine partials, each as (frequency, amplitude)
+pub fn partial_dissonance((f1, a1): (f64, f64), (f2, a2): (f64, f64)) -> f64 {
+	let low = f1.min(f2);
+	let s = X_STAR / (S1 * low + S2);
+	let diff = (f2 - f1).abs();
+	a1.min(a2) * ((-B1 * s * diff).exp() - (-B2 * s * diff).exp())
+}
+
+//total roughness of every pair of partials
+pub fn spectrum_dissonance(spectrum: &[(f64, f64)]) -> f64 {
+	let mut total = 0.0;
+	for (n, p) in spectrum.iter().enumerate() {
+		for q in &spectrum[n + 1..] {
+			total += partial_dissonance(*p, *q);
+		}
+	}
+	total
+}
+
+//two notes with the same timbre, the lower one at base Hz
+//partials are (harmonic number, amplitude), like Timbre::partials
+pub fn dissonance(base: f64, cents: f64, partials: &[(f64, f64)]) -> f64 {
+	let upper = base * (cents / 1200.0).exp2();
+	let spectrum: Vec<(f64, f64)> = partials.iter().map(|(k, a)| (base * k, *a))
+		.chain(partials.iter().map(|(k, a)| (upper * k, *a)))
+		.collect();
+	spectrum_dissonance(&spectrum)
+}
+
+impl Interval {
+	pub fn dissonance(&self, base: f64, partials: &[(f64, f64)]) -> f64 {
+		dissonance(base, self.cents(), partials)
+	}
+}
+
+pub struct DissonancePoint {
+	pub cents: f64,
+	pub dissonance: f64,
+	pub mark: DissonanceMark
+}
+
+pub enum DissonanceMark {
+	//a point of the evenly spaced curve
+	Curve,
+	//a named interval
+	Named(Interval, &'static str),
+	//a step of an EDO
+	Edo{div: u64, step: i64}
+}
+
+//the curve every step cents from min to max, with the named intervals and the steps of each EDO in the range added in order
+pub fn dissonance_curve(base: f64, partials: &[(f64, f64)], min: f64, max: f64, step: f64, edos: &[u64]) -> Vec<DissonancePoint> {
+	let point = |cents: f64, mark| DissonancePoint{cents, dissonance: dissonance(base, cents, partials), mark};
+	let mut points = Vec::new();
+	
+	if step > 0.0 {
+		let count = ((max - min) / step).floor() as u64;
+		for n in 0..=count {
+			points.push(point(min + n as f64 * step, DissonanceMark::Curve));
+		}
+	}
+	
+	for n in NAMES.iter() {
+		let cents = n.interval.cents();
+		if (min..=max).contains(&cents) {
+			points.push(point(cents, DissonanceMark::Named(n.interval, n.name)));
+		}
+	}
+	
+	for div in edos.iter().filter(|d| **d > 0) {
+		let size = 1200.0 / *div as f64;
+		for s in (min / size).ceil() as i64..=(max / size).floor() as i64 {
+			points.push(point(s as f64 * size, DissonanceMark::Edo{div: *div, step: s}));
+		}
+	}
+	
+	points.sort_by(|a, b| a.cents.total_cmp(&b.cents));
+	points
+}
+
+//cents,dissonance,kind,label with a header line
+pub fn dissonance_csv(points: &[DissonancePoint]) -> String {
+	let mut csv = String::from("cents,dissonance,kind,label\n");
+	for p in points {
+		let (kind, label) = match &p.mark {
+			DissonanceMark::Curve => ("curve", String::new()),
+			DissonanceMark::Named(i, name) => ("name", format!("{} {}", i, name)),
+			DissonanceMark::Edo{div, step} => ("edo", format!("{}\\{}", step, div)),
+		};
+		csv.push_str(&format!("{},{},{},{}\n", p.cents, p.dissonance, kind, csv_field(&label)));
+	}
+	csv
+}
+
+//quoted if it has a comma or a quote in it
+pub(crate) fn csv_field(s: &str) -> String {
+	if s.contains([',', '"', '\n']) {
+		format!("\"{}\"", s.replace('"', "\"\""))
+	} else {
+		s.to_string()
+	}
+}
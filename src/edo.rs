@@ -0,0 +1,30 @@
+//finding the equal divisions of the octave that best approximate a set of intervals
+
+use crate::{closest_edo_note, ScaleApproximation, ScaleError};
+
+pub struct EdoRanking {
+	pub div: u64,
+	//root mean square of the errors, in cents or in steps of the EDO if relative
+	pub error: f64,
+	//closest note for each interval, in the same order
+	pub approximations: Vec<ScaleApproximation>
+}
+
+//ranks every EDO from min_div to max_div by how well it approximates all of the intervals, best first
+//relative error is the error divided by the step size, so large EDOs do not win by default
+pub fn rank_edos(cents: &[f64], min_div: u64, max_div: u64, relative: bool) -> Result<Vec<EdoRanking>, ScaleError> {
+	let mut rankings = (min_div.max(1)..=max_div).map(|div| {
+		let approximations = cents.iter()
+			.map(|c| closest_edo_note(*c, div, true))
+			.collect::<Result<Vec<ScaleApproximation>, ScaleError>>()?;
+		
+		let step = if relative { 1200.0 / div as f64 } else { 1.0 };
+		let sum: f64 = approximations.iter().map(|a| (a.offset / step).powi(2)).sum();
+		let error = (sum / approximations.len().max(1) as f64).sqrt();
+		
+		Ok(EdoRanking{div, error, approximations})
+	}).collect::<Result<Vec<EdoRanking>, ScaleError>>()?;
+	
+	rankings.sort_by(|a, b| a.error.total_cmp(&b.error).then(a.div.cmp(&b.div)));
+	Ok(rankings)
+}
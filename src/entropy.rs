@@ -0,0 +1,175 @@
+//harmonic entropy, how ambiguous an interval is as a ratio, after Paul Erlich
+//the heard interval is spread out by a normal distribution, the entropy is of the chances of hearing each ratio
+
+use std::{fmt, str::FromStr};
+use crate::{gcd, Interval};
+
+//probabilities are only summed this many spreads away, past that they are too small to matter
+const WINDOW: f64 = 8.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RatioSet {
+	//numerator and denominator up to the limit, each ratio gets the part of the curve closer to it than to its neighbours
+	Farey,
+	//numerator times denominator up to the limit, weighted by 1/sqrt(n*d)
+	Tenney
+}
+
+impl RatioSet {
+	//about as many ratios for either set
+	pub fn default_limit(&self) -> u128 {
+		match self {
+			RatioSet::Farey => 100,
+			RatioSet::Tenney => 10000,
+		}
+	}
+	
+	//past this there are too many ratios to keep in memory
+	pub fn max_limit(&self) -> u128 {
+		match self {
+			RatioSet::Farey => 1000,
+			RatioSet::Tenney => 100000,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownRatioSet(pub String);
+
+impl fmt::Display for UnknownRatioSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "unknown ratio set {}, use farey or tenney", self.0)
+	}
+}
+
+impl std::error::Error for UnknownRatioSet {}
+
+impl FromStr for RatioSet {
+	type Err = UnknownRatioSet;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim() {
+			"farey" => Ok(RatioSet::Farey),
+			"tenney" => Ok(RatioSet::Tenney),
+			_ => Err(UnknownRatioSet(s.to_string())),
+		}
+	}
+}
+
+impl fmt::Display for RatioSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			RatioSet::Farey => write!(f, "farey"),
+			RatioSet::Tenney => write!(f, "tenney"),
+		}
+	}
+}
+
+pub struct HarmonicEntropy {
+	pub set: RatioSet,
+	pub limit: u128,
+	//standard deviation of the hearing of an interval, in cents
+	pub spread: f64,
+	//in order of cents, with the ratios below 1/1 as well so the curve is even around the unison
+	ratios: Vec<EntropyRatio>
+}
+
+struct EntropyRatio {
+	cents: f64,
+	//weight for the tenney set, edges of the area closest to the ratio for the farey set
+	weight: f64,
+	low: f64,
+	high: f64
+}
+
+impl HarmonicEntropy {
+	pub fn new(set: RatioSet, limit: u128, spread: f64) -> Self {
+		let mut intervals = Vec::new();
+		for den in 1..=limit {
+			let max_num = match set {
+				RatioSet::Farey => limit,
+				RatioSet::Tenney => limit / den,
+			};
+			for num in den..=max_num {
+				if gcd(num, den) == 1 {
+					intervals.push(Interval::new(num, den));
+				}
+			}
+		}
+		let mut below: Vec<Interval> = intervals.iter().filter(|i| i.num != i.den).map(|i| i.inverse()).collect();
+		intervals.append(&mut below);
+		intervals.sort();
+		
+		let cents: Vec<f64> = intervals.iter().map(|i| i.cents()).collect();
+		let ratios = intervals.iter().enumerate().map(|(n, i)| {
+			//the mediant is where the areas of neighbouring farey ratios meet
+			let low = match n {
+				0 => f64::NEG_INFINITY,
+				_ => Interval::new(i.num + intervals[n - 1].num, i.den + intervals[n - 1].den).cents(),
+			};
+			let high = match intervals.get(n + 1) {
+				Some(next) => Interval::new(i.num + next.num, i.den + next.den).cents(),
+				None => f64::INFINITY,
+			};
+			let weight = 1.0 / ((i.num * i.den) as f64).sqrt();
+			EntropyRatio{cents: cents[n], weight, low, high}
+		}).collect();
+		
+		Self{set, limit, spread, ratios}
+	}
+	
+	//in nats, higher is more ambiguous
+	pub fn entropy(&self, cents: f64) -> f64 {
+		let s = self.spread;
+		let start = self.ratios.partition_point(|r| r.cents < cents - WINDOW * s);
+		let end = self.ratios.partition_point(|r| r.cents <= cents + WINDOW * s);
+		let window = &self.ratios[start..end];
+		
+		let chances: Vec<f64> = window.iter().map(|r| match self.set {
+			RatioSet::Farey => normal_cdf((r.high - cents) / s) - normal_cdf((r.low - cents) / s),
+			RatioSet::Tenney => r.weight * (-(r.cents - cents).powi(2) / (2.0 * s * s)).exp(),
+		}).collect();
+		
+		let total: f64 = chances.iter().sum();
+		if total <= 0.0 {
+			return 0.0;
+		}
+		-chances.iter()
+			.map(|p| p / total)
+			.filter(|p| *p > 0.0)
+			.map(|p| p * p.ln())
+			.sum::<f64>()
+	}
+	
+	//(cents, entropy) every step cents from min to max
+	pub fn curve(&self, min: f64, max: f64, step: f64) -> Vec<(f64, f64)> {
+		let count = ((max - min) / step).floor() as u64;
+		(0..=count).map(|n| {
+			let cents = min + n as f64 * step;
+			(cents, self.entropy(cents))
+		}).collect()
+	}
+}
+
+//cents,entropy with a header line
+pub fn entropy_csv(curve: &[(f64, f64)]) -> String {
+	let mut csv = String::from("cents,entropy\n");
+	for (cents, entropy) in curve {
+		csv.push_str(&format!("{},{}\n", cents, entropy));
+	}
+	csv
+}
+
+fn normal_cdf(x: f64) -> f64 {
+	0.5 * (1.0 + erf(x / 2f64.sqrt()))
+}
+
+//Abramowitz and Stegun 7.1.26, accurate to about 1.5e-7
+fn erf(x: f64) -> f64 {
+	if x.is_infinite() {
+		return x.signum();
+	}
+	let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+	let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+	(1.0 - poly * (-x * x).exp()).copysign(x)
+}
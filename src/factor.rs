@@ -0,0 +1,273 @@
+//prime testing and factorization for the whole u128 range
+//Miller-Rabin for primality and Pollard's rho (Brent's variant) to split composites
+
+use crate::{gcd, wide_mul};
+
+//trial division is faster for these, and it leaves pollard rho only large factors
+const SMALL_PRIMES: [u128; 25] = [
+	2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97
+];
+
+//the first 13 are enough to be exact below 3.3*10^24, the rest make a false positive above that astronomically unlikely
+const WITNESSES: [u128; 20] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71];
+
+pub fn is_prime(n: u128) -> bool {
+	if n < 2 {
+		return false;
+	}
+	for p in SMALL_PRIMES {
+		if n == p {
+			return true;
+		}
+		if n.is_multiple_of(p) {
+			return false;
+		}
+	}
+	if n < 101 * 101 {
+		return true;
+	}
+	
+	let m = Montgomery::new(n);
+	let d = (n - 1) >> (n - 1).trailing_zeros();
+	WITNESSES.iter().all(|a| strong_probable_prime(&m, d, *a))
+}
+
+//everything is in montgomery form, where 1 is m.one and n-1 is n - m.one
+fn strong_probable_prime(m: &Montgomery, mut d: u128, a: u128) -> bool {
+	let n = m.n;
+	let minus_one = n - m.one;
+	let mut x = m.pow(m.to(a), d);
+	if x == m.one || x == minus_one {
+		return true;
+	}
+	while d != n - 1 {
+		x = m.mul(x, x);
+		d <<= 1;
+		if x == minus_one {
+			return true;
+		}
+		if x == m.one {
+			return false;
+		}
+	}
+	false
+}
+
+//steps of pollard rho before giving up on a composite, it finds factors up to about the square of this
+const RHO_STEP_LIMIT: u128 = 1 << 20;
+
+//prime factors from smallest to largest, with repetition. 1 has none
+//a composite made of primes too large for pollard rho to find in time is left in unfactored, check with is_prime
+pub fn factorize(mut n: u128) -> Vec<u128> {
+	let mut factors = Vec::new();
+	if n < 2 {
+		return factors;
+	}
+	
+	for p in SMALL_PRIMES {
+		while n.is_multiple_of(p) {
+			n /= p;
+			factors.push(p);
+		}
+	}
+	
+	let mut stack = vec![n];
+	while let Some(n) = stack.pop() {
+		if n == 1 {
+			continue;
+		}
+		if is_prime(n) {
+			factors.push(n);
+			continue;
+		}
+		//pollard rho is slowest on powers of a large prime, so those are found directly
+		if let Some((root, power)) = perfect_power(n) {
+			stack.extend(std::iter::repeat_n(root, power as usize));
+			continue;
+		}
+		match pollard_rho(n) {
+			Some(d) => {
+				stack.push(d);
+				stack.push(n / d);
+			}
+			None => factors.push(n),
+		}
+	}
+	
+	factors.sort();
+	factors
+}
+
+//smallest root and its power if n is a power of a whole number, n has no factors below 101 here
+fn perfect_power(n: u128) -> Option<(u128, u32)> {
+	//101^20 is past u128::MAX
+	(2..20).rev().find_map(|power| {
+		let root = integer_root(n, power);
+		(root > 1 && root.pow(power) == n).then_some((root, power))
+	})
+}
+
+//largest r with r^power <= n
+fn integer_root(n: u128, power: u32) -> u128 {
+	let fits = |r: u128| r.checked_pow(power).is_some_and(|p| p <= n);
+	//f64 only gets close for large n, so the exact root is searched for around its estimate
+	let estimate = (n as f64).powf(1.0 / power as f64);
+	let mut low = ((estimate * (1.0 - 1e-9)) as u128).max(1);
+	while low > 1 && !fits(low) {
+		low /= 2;
+	}
+	let mut high = (estimate * (1.0 + 1e-9)) as u128 + 1;
+	while fits(high) {
+		high *= 2;
+	}
+	while high - low > 1 {
+		let mid = low + (high - low) / 2;
+		if fits(mid) {
+			low = mid;
+		} else {
+			high = mid;
+		}
+	}
+	low
+}
+
+//a nontrivial divisor of a composite n that has no small prime factors, None if it takes too long
+//works in montgomery form, which changes the values but not their gcd with n
+fn pollard_rho(n: u128) -> Option<u128> {
+	let m = Montgomery::new(n);
+	
+	//rho with batched gcds, retried with another polynomial if it cycles without finding a factor
+	for c in 1..=16 {
+		let f = |x: u128| add_mod(m.mul(x, x), c, n);
+		let batch = 128;
+		
+		let mut y: u128 = 2;
+		let mut x = y;
+		let mut ys = y;
+		let mut q: u128 = 1;
+		let mut g: u128 = 1;
+		let mut r: u128 = 1;
+		
+		while g == 1 {
+			if r > RHO_STEP_LIMIT {
+				return None;
+			}
+			x = y;
+			for _ in 0..r {
+				y = f(y);
+			}
+			let mut k = 0;
+			while k < r && g == 1 {
+				ys = y;
+				for _ in 0..batch.min(r - k) {
+					y = f(y);
+					q = m.mul(q, x.abs_diff(y));
+				}
+				g = gcd(q, n);
+				k += batch;
+			}
+			r *= 2;
+		}
+		
+		//the batch overshot, go back one step at a time
+		if g == n {
+			loop {
+				ys = f(ys);
+				g = gcd(x.abs_diff(ys), n);
+				if g > 1 {
+					break;
+				}
+			}
+		}
+		
+		if g != n {
+			return Some(g);
+		}
+	}
+	None
+}
+
+fn add_mod(a: u128, b: u128, n: u128) -> u128 {
+	let (sum, overflow) = a.overflowing_add(b);
+	if overflow || sum >= n {
+		sum.wrapping_sub(n)
+	} else {
+		sum
+	}
+}
+
+//only used to set up montgomery multiplication, which is much faster
+fn slow_mul_mod(a: u128, b: u128, n: u128) -> u128 {
+	//double and add, one bit of b at a time
+	let mut result = 0;
+	let mut a = a % n;
+	let mut b = b;
+	while b > 0 {
+		if b & 1 == 1 {
+			result = add_mod(result, a, n);
+		}
+		a = add_mod(a, a, n);
+		b >>= 1;
+	}
+	result
+}
+
+//multiplication modulo an odd n without 256 bit division, using R = 2^128
+//a number x is stored as x*R mod n
+struct Montgomery {
+	n: u128,
+	//-1/n mod R
+	n_neg_inv: u128,
+	//R mod n, which is 1 in montgomery form
+	one: u128,
+	//R^2 mod n, for converting into montgomery form
+	r2: u128
+}
+
+impl Montgomery {
+	fn new(n: u128) -> Self {
+		//newton's method, each step doubles the number of correct low bits
+		let mut inv: u128 = 1;
+		for _ in 0..7 {
+			inv = inv.wrapping_mul(2u128.wrapping_sub(n.wrapping_mul(inv)));
+		}
+		let one = (u128::MAX % n + 1) % n;
+		let r2 = slow_mul_mod(one, one, n);
+		Self{n, n_neg_inv: inv.wrapping_neg(), one, r2}
+	}
+	
+	//x/R mod n for x = high*R + low < n*R
+	fn reduce(&self, (high, low): (u128, u128)) -> u128 {
+		let m = low.wrapping_mul(self.n_neg_inv);
+		let (mn_high, mn_low) = wide_mul(m, self.n);
+		//low + mn_low is a multiple of R, so only the carry is left
+		let (_, carry) = low.overflowing_add(mn_low);
+		let (t, overflow1) = high.overflowing_add(mn_high);
+		let (t, overflow2) = t.overflowing_add(carry as u128);
+		if overflow1 || overflow2 || t >= self.n {
+			t.wrapping_sub(self.n)
+		} else {
+			t
+		}
+	}
+	
+	fn mul(&self, a: u128, b: u128) -> u128 {
+		self.reduce(wide_mul(a, b))
+	}
+	
+	fn to(&self, a: u128) -> u128 {
+		self.mul(a % self.n, self.r2)
+	}
+	
+	fn pow(&self, mut base: u128, mut exp: u128) -> u128 {
+		let mut result = self.one;
+		while exp > 0 {
+			if exp & 1 == 1 {
+				result = self.mul(result, base);
+			}
+			base = self.mul(base, base);
+			exp >>= 1;
+		}
+		result
+	}
+}
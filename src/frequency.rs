@@ -0,0 +1,137 @@
+//absolute pitch, with 12-TET note names and MIDI notes tuned to A4 = 440 Hz
+
+use std::{fmt, str::FromStr};
+
+pub const A4_FREQUENCY: f64 = 440.0;
+pub const A4_MIDI_NOTE: i64 = 69;
+//pitch bend is 14 bit, with no bend in the middle
+pub const PITCH_BEND_CENTER: u16 = 8192;
+pub const PITCH_BEND_MAX: u16 = 16383;
+
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+//a frequency in Hz, written as a number like 440 or 261.63hz, or a 12-TET note name like A4, C#5 or Bb3
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Frequency(pub f64);
+
+impl Frequency {
+	pub fn of_midi_note(note: i64) -> Self {
+		Frequency(A4_FREQUENCY * ((note - A4_MIDI_NOTE) as f64 / 12.0).exp2())
+	}
+	
+	//the interval above this frequency
+	pub fn above(&self, cents: f64) -> Self {
+		Frequency(self.0 * (cents / 1200.0).exp2())
+	}
+	
+	//None if the frequency is 0, infinite, or so far out the note number would not fit in i64
+	pub fn nearest_midi_note(&self) -> Option<MidiNote> {
+		let semitones = 12.0 * (self.0 / A4_FREQUENCY).log2();
+		if !(semitones.is_finite() && semitones.abs() < (i64::MAX / 2) as f64) {
+			return None;
+		}
+		let note = semitones.round();
+		Some(MidiNote{note: note as i64 + A4_MIDI_NOTE, offset: (semitones - note) * 100.0})
+	}
+}
+
+impl fmt::Display for Frequency {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} Hz", self.0)
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FrequencyParseError {
+	Invalid(String),
+	NotPositive
+}
+
+impl fmt::Display for FrequencyParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			FrequencyParseError::Invalid(s) => write!(f, "not a frequency or note name like 440 or A4: {}", s),
+			FrequencyParseError::NotPositive => write!(f, "frequency has to be larger than 0 Hz"),
+		}
+	}
+}
+
+impl std::error::Error for FrequencyParseError {}
+
+impl FromStr for Frequency {
+	type Err = FrequencyParseError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let invalid = || FrequencyParseError::Invalid(s.to_string());
+		
+		if s.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+			let hz = s.strip_suffix("Hz").or(s.strip_suffix("hz")).unwrap_or(s);
+			let hz = hz.trim().parse::<f64>().map_err(|_| invalid())?;
+			if !(hz.is_finite() && hz > 0.0) {
+				return Err(FrequencyParseError::NotPositive);
+			}
+			return Ok(Frequency(hz));
+		}
+		
+		let mut chars = s.chars();
+		let letter = chars.next().ok_or_else(invalid)?.to_ascii_uppercase();
+		let mut pitch_class = NOTE_NAMES.iter().position(|n| n.starts_with(letter) && n.len() == 1).ok_or_else(invalid)? as i64;
+		let mut rest = chars.as_str();
+		while let Some(accidental) = rest.chars().next() {
+			match accidental {
+				'#' | '♯' => pitch_class += 1,
+				'b' | '♭' => pitch_class -= 1,
+				_ => break,
+			}
+			rest = &rest[accidental.len_utf8()..];
+		}
+		let octave = rest.parse::<i64>().map_err(|_| invalid())?;
+		Ok(Frequency::of_midi_note((octave + 1) * 12 + pitch_class))
+	}
+}
+
+//the closest 12-TET note, C4 is 60
+pub struct MidiNote {
+	pub note: i64,
+	//cents from the note to the frequency, between -50 and 50
+	pub offset: f64
+}
+
+impl MidiNote {
+	//with sharps, e.g. C#4
+	pub fn name(&self) -> String {
+		format!("{}{}", NOTE_NAMES[self.note.rem_euclid(12) as usize], self.note.div_euclid(12) - 1)
+	}
+	
+	//14 bit pitch bend to reach the frequency from the note, for a bend range in semitones up and down
+	//None if the offset is past the range
+	pub fn pitch_bend(&self, range: f64) -> Option<u16> {
+		let bend = self.offset / (range * 100.0);
+		if !(-1.0..=1.0).contains(&bend) {
+			return None;
+		}
+		let value = PITCH_BEND_CENTER as f64 + (bend * PITCH_BEND_CENTER as f64).round();
+		Some((value as u16).min(PITCH_BEND_MAX))
+	}
+}
+
+//absolute pitch of an interval above a reference frequency
+pub struct FrequencyReport {
+	pub base: Frequency,
+	pub frequency: Frequency,
+	//None if the frequency is out of range
+	pub midi: Option<MidiNote>,
+	//semitones
+	pub bend_range: f64,
+	pub pitch_bend: Option<u16>
+}
+
+impl FrequencyReport {
+	pub fn new(base: Frequency, cents: f64, bend_range: f64) -> Self {
+		let frequency = base.above(cents);
+		let midi = frequency.nearest_midi_note();
+		let pitch_bend = midi.as_ref().and_then(|m| m.pitch_bend(bend_range));
+		Self{base, frequency, midi, bend_range, pitch_bend}
+	}
+}
@@ -0,0 +1,166 @@
+//minimal JSON values for machine readable output, only writing is supported
+//{} writes it on one line, {:#} indents it with tabs
+
+use std::fmt;
+
+pub enum Json {
+	Null,
+	Bool(bool),
+	//written as is, so u128 values keep all of their digits
+	Int(i128),
+	UInt(u128),
+	//null if not finite, JSON has no NaN or infinity
+	Float(f64),
+	String(String),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>)
+}
+
+pub trait ToJson {
+	fn to_json(&self) -> Json;
+}
+
+impl Json {
+	//keys stay in the order they are added
+	pub fn object() -> Self {
+		Json::Object(Vec::new())
+	}
+	
+	//only does something for objects
+	pub fn with(mut self, key: &str, value: impl Into<Json>) -> Self {
+		if let Json::Object(fields) = &mut self {
+			fields.push((key.to_string(), value.into()));
+		}
+		self
+	}
+	
+	fn write(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+		let pretty = f.alternate();
+		let (open, close) = match self {
+			Json::Array(_) => ('[', ']'),
+			Json::Object(_) => ('{', '}'),
+			Json::Null => return write!(f, "null"),
+			Json::Bool(b) => return write!(f, "{}", b),
+			Json::Int(n) => return write!(f, "{}", n),
+			Json::UInt(n) => return write!(f, "{}", n),
+			Json::Float(x) if x.is_finite() => return write!(f, "{}", x),
+			Json::Float(_) => return write!(f, "null"),
+			Json::String(s) => return write_string(f, s),
+		};
+		
+		let len = match self {
+			Json::Array(values) => values.len(),
+			Json::Object(fields) => fields.len(),
+			_ => unreachable!()
+		};
+		write!(f, "{}", open)?;
+		if len == 0 {
+			return write!(f, "{}", close);
+		}
+		
+		for n in 0..len {
+			if n > 0 {
+				write!(f, ",")?;
+			}
+			if pretty {
+				write!(f, "\n{}", "\t".repeat(depth + 1))?;
+			}
+			match self {
+				Json::Array(values) => values[n].write(f, depth + 1)?,
+				Json::Object(fields) => {
+					write_string(f, &fields[n].0)?;
+					write!(f, ":{}", if pretty { " " } else { "" })?;
+					fields[n].1.write(f, depth + 1)?;
+				}
+				_ => unreachable!()
+			}
+		}
+		
+		if pretty {
+			write!(f, "\n{}", "\t".repeat(depth))?;
+		}
+		write!(f, "{}", close)
+	}
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+	write!(f, "\"")?;
+	for c in s.chars() {
+		match c {
+			'"' => write!(f, "\\\"")?,
+			'\\' => write!(f, "\\\\")?,
+			'\n' => write!(f, "\\n")?,
+			'\r' => write!(f, "\\r")?,
+			'\t' => write!(f, "\\t")?,
+			c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+			c => write!(f, "{}", c)?,
+		}
+	}
+	write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.write(f, 0)
+	}
+}
+
+impl From<bool> for Json {
+	fn from(b: bool) -> Self {
+		Json::Bool(b)
+	}
+}
+
+impl From<i64> for Json {
+	fn from(n: i64) -> Self {
+		Json::Int(n as i128)
+	}
+}
+
+impl From<i32> for Json {
+	fn from(n: i32) -> Self {
+		Json::Int(n as i128)
+	}
+}
+
+impl From<u128> for Json {
+	fn from(n: u128) -> Self {
+		Json::UInt(n)
+	}
+}
+
+impl From<u64> for Json {
+	fn from(n: u64) -> Self {
+		Json::UInt(n as u128)
+	}
+}
+
+impl From<f64> for Json {
+	fn from(x: f64) -> Self {
+		Json::Float(x)
+	}
+}
+
+impl From<&str> for Json {
+	fn from(s: &str) -> Self {
+		Json::String(s.to_string())
+	}
+}
+
+impl From<String> for Json {
+	fn from(s: String) -> Self {
+		Json::String(s)
+	}
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+	fn from(value: Option<T>) -> Self {
+		value.map_or(Json::Null, |v| v.into())
+	}
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+	fn from(values: Vec<T>) -> Self {
+		Json::Array(values.into_iter().map(|v| v.into()).collect())
+	}
+}
@@ -1,13 +1,45 @@
+use std::{cmp::{self, Ordering}, fmt, num::IntErrorKind, ops, str::FromStr};
 
-//maybe make u128 so that the last 2 intervals also fit
-#[derive(PartialEq)]
+mod audio;
+mod beats;
+mod chord;
+mod combination;
+mod dissonance;
+mod edo;
+mod entropy;
+mod factor;
+mod frequency;
+mod json;
+mod monzo;
+mod pitch;
+mod rational;
+mod report;
+mod scala;
+pub use audio::*;
+pub use beats::*;
+pub use chord::*;
+pub use combination::*;
+pub use dissonance::*;
+pub use edo::*;
+pub use entropy::*;
+pub use factor::*;
+pub use frequency::*;
+pub use json::*;
+pub use monzo::*;
+pub use pitch::*;
+pub use rational::*;
+pub use report::*;
+pub use scala::*;
+
+//u128 so that large commas like Mercator's comma fit
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub struct Interval {
-	pub num: u64,
-	pub den: u64
+	pub num: u128,
+	pub den: u128
 }
 
 impl Interval {
-	pub fn new(num: u64, den: u64) -> Self {
+	pub fn new(num: u128, den: u128) -> Self {
 		Self{num,den}
 	}
 	
@@ -38,502 +70,710 @@ impl Interval {
 	}
 	
 	//returns common factor reduced by. 1 means already in reduced form
-	pub fn reduce(&mut self) -> u64 {
-		let mut reduced = 1;
-		let mut factor = 2;
-		while factor <= self.num {
-			if self.num % factor == 0 && self.den % factor == 0 {
-				self.num /= factor;
-				self.den /= factor;
-				reduced *= factor;
-			} else {
-				factor += 1;
-			}
+	pub fn reduce(&mut self) -> u128 {
+		let reduced = gcd(self.num, self.den);
+		if reduced > 1 {
+			self.num /= reduced;
+			self.den /= reduced;
 		}
 		reduced
 	}
 	
-	pub fn from_str(s: &str) -> Self {
-		let pivot = s.chars().position(|c| c == '/').unwrap_or(0);
-		let num = s[..pivot].parse::<u64>().unwrap_or(1);
-		let den = s[pivot+1..].parse::<u64>().unwrap_or(1);
-		Self::new(num,den)
+	pub fn reduced(mut self) -> Self {
+		self.reduce();
+		self
+	}
+	
+	//flips the interval, 3/2 becomes 2/3
+	pub fn inverse(self) -> Self {
+		Self::new(self.den, self.num)
+	}
+	
+	//None on overflow. common factors are cancelled before multiplying to avoid overflow where possible
+	pub fn checked_mul(self, other: Self) -> Option<Self> {
+		let a = gcd(self.num, other.den);
+		let b = gcd(other.num, self.den);
+		let num = (self.num / a).checked_mul(other.num / b)?;
+		let den = (self.den / b).checked_mul(other.den / a)?;
+		Some(Self::new(num, den).reduced())
+	}
+	
+	pub fn checked_div(self, other: Self) -> Option<Self> {
+		self.checked_mul(other.inverse())
+	}
+	
+	//interval stacked exp times, negative exponents stack the inverse
+	pub fn checked_pow(self, exp: i32) -> Option<Self> {
+		let i = if exp < 0 { self.inverse() } else { self }.reduced();
+		Some(Self::new(i.num.checked_pow(exp.unsigned_abs())?, i.den.checked_pow(exp.unsigned_abs())?))
+	}
+	
+	pub fn pow(self, exp: i32) -> Self {
+		self.checked_pow(exp).expect("interval pow overflowed")
+	}
+	
+	//octave reduced into [1/1, 2/1)
+	pub fn normalize(self) -> Self {
+		self.normalize_to(Self::new(2,1))
+	}
+	
+	pub fn normalize_to(self, period: Self) -> Self {
+		self.checked_normalize_to(period).expect("normalized interval overflowed")
+	}
+	
+	pub fn checked_normalize(self) -> Option<Self> {
+		self.checked_normalize_to(Self::new(2,1))
+	}
+	
+	//moved by whole periods into [1/1, period), period has to be larger than 1/1
+	//None if the result does not fit, e.g. 1/u128::MAX would need 2^128 as numerator
+	pub fn checked_normalize_to(self, period: Self) -> Option<Self> {
+		let unison = Self::new(1,1);
+		assert!(period > unison, "period has to be larger than 1/1");
+		let mut i = self.reduced();
+		let mut periods = (i.cents() / period.cents()).floor() as i32;
+		//near the u128 limits the power of the period, or the float estimate of how many periods, can overflow
+		//so it is moved in smaller steps there, and the rest is left for the loops below
+		'moving: while periods != 0 {
+			let mut step = periods;
+			loop {
+				if let Some(moved) = period.checked_pow(step).and_then(|p| i.checked_div(p)) {
+					i = moved;
+					break;
+				}
+				step /= 2;
+				if step == 0 {
+					break 'moving;
+				}
+			}
+			periods -= step;
+		}
+		//float rounding can leave it one period off
+		while i < unison {
+			i = i.checked_mul(period)?;
+		}
+		while i >= period {
+			i = i.checked_div(period)?;
+		}
+		Some(i)
+	}
+	
+	//the interval that makes an octave when stacked on the normalized interval, e.g. 3/2 for 4/3
+	pub fn complement(self) -> Self {
+		Self::new(2,1) / self.normalize()
+	}
+	
+	//complexity metrics, all of them are for the interval in lowest terms
+	
+	//log2(n*d)
+	pub fn tenney_height(&self) -> f64 {
+		let i = self.reduced();
+		(i.num as f64).log2() + (i.den as f64).log2()
+	}
+	
+	//n*d, None if it overflows
+	pub fn benedetti_height(&self) -> Option<u128> {
+		let i = self.reduced();
+		i.num.checked_mul(i.den)
+	}
+	
+	//max(n,d)
+	pub fn weil_height(&self) -> u128 {
+		let i = self.reduced();
+		cmp::max(i.num, i.den)
+	}
+	
+	//sum of the prime factors of n*d, with repetition, None if it overflows
+	pub fn wilson_complexity(&self) -> Option<u128> {
+		FactorMetrics::new(&self.prime_factors()).wilson_complexity
+	}
+	
+	//Euler's gradus suavitatis, 1 + sum of (p-1) for each prime factor of n*d, with repetition, None if it overflows
+	pub fn gradus_suavitatis(&self) -> Option<u128> {
+		FactorMetrics::new(&self.prime_factors()).gradus_suavitatis
+	}
+	
+	//largest odd number in the ratio once factors of 2 are removed, e.g. 9 for 9/8
+	pub fn odd_limit(&self) -> u128 {
+		let i = self.reduced();
+		cmp::max(i.num >> i.num.trailing_zeros(), i.den >> i.den.trailing_zeros())
+	}
+	
+	//largest number in the ratio, same as the Weil height
+	pub fn integer_limit(&self) -> u128 {
+		self.weil_height()
+	}
+	
+	//primes that the interval is made of, e.g. [2, 3, 7] for 7/6
+	pub fn prime_subgroup(&self) -> Vec<u128> {
+		FactorMetrics::new(&self.prime_factors()).prime_subgroup
+	}
+	
+	//prime factors of n and d together
+	fn prime_factors(&self) -> Vec<(u128, u8)> {
+		let i = self.reduced();
+		let num = PrimeFactors::init(i.num);
+		let den = PrimeFactors::init(i.den);
+		num.iter().chain(den.iter()).collect()
 	}
 }
 
-pub fn largest_prime_factor(mut n: u64) -> u64 {
-	let mut limit = 1;
-	let mut factor = 2;
+impl ops::Mul for Interval {
+	type Output = Self;
 	
-	while 1 < n {
-		if n % factor == 0 {
-			n /= factor;
-			limit = factor
-		} else {
-			factor += 1;
+	fn mul(self, other: Self) -> Self {
+		self.checked_mul(other).expect("interval multiplication overflowed")
+	}
+}
+
+impl ops::Div for Interval {
+	type Output = Self;
+	
+	fn div(self, other: Self) -> Self {
+		self.checked_div(other).expect("interval division overflowed")
+	}
+}
+
+//by size, ties between equal ratios not in lowest terms go to the smaller numerator
+impl Ord for Interval {
+	fn cmp(&self, other: &Self) -> Ordering {
+		wide_mul(self.num, other.den).cmp(&wide_mul(other.num, self.den))
+			.then(self.num.cmp(&other.num))
+	}
+}
+
+impl PartialOrd for Interval {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl fmt::Display for Interval {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}/{}", self.num, self.den)
+	}
+}
+
+//full 256 bit product as (high, low), so comparisons can not overflow
+pub(crate) fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+	let mask = u64::MAX as u128;
+	let (a1, a0) = (a >> 64, a & mask);
+	let (b1, b0) = (b >> 64, b & mask);
+	
+	let low = a0 * b0;
+	let mid1 = a1 * b0;
+	let mid2 = a0 * b1;
+	let high = a1 * b1;
+	
+	let (mid, mid_carry) = mid1.overflowing_add(mid2);
+	let (low, low_carry) = low.overflowing_add(mid << 64);
+	let high = high + (mid >> 64) + ((mid_carry as u128) << 64) + low_carry as u128;
+	(high, low)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IntervalParseError {
+	Empty,
+	NonNumeric(String),
+	Zero,
+	Overflow,
+	MissingSeparator,
+	Monzo(MonzoParseError),
+	NotFinite,
+	//further from 1/1 than MAX_CENTS
+	OutOfRange
+}
+
+impl fmt::Display for IntervalParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			IntervalParseError::Empty => write!(f, "empty interval"),
+			IntervalParseError::NonNumeric(s) => write!(f, "not a number: {}", s),
+			IntervalParseError::Zero => write!(f, "0 is not a valid numerator, denominator or division"),
+			IntervalParseError::Overflow => write!(f, "number too large, max is {}", u128::MAX),
+			IntervalParseError::MissingSeparator => write!(f, "missing '/' between numerator and denominator"),
+			IntervalParseError::Monzo(e) => write!(f, "{}", e),
+			IntervalParseError::NotFinite => write!(f, "interval is too large to calculate"),
+			IntervalParseError::OutOfRange => write!(f, "interval is more than {} cents away from 1/1", MAX_CENTS),
+		}
+	}
+}
+
+impl std::error::Error for IntervalParseError {}
+
+impl From<MonzoParseError> for IntervalParseError {
+	fn from(e: MonzoParseError) -> Self {
+		IntervalParseError::Monzo(e)
+	}
+}
+
+//parses a whole number, 0 is allowed so the caller can decide what it means
+pub(crate) fn parse_u128(s: &str) -> Result<u128, IntervalParseError> {
+	s.parse::<u128>().map_err(|e| match e.kind() {
+		IntErrorKind::Empty => IntervalParseError::Empty,
+		IntErrorKind::PosOverflow => IntervalParseError::Overflow,
+		_ => IntervalParseError::NonNumeric(s.to_string()),
+	})
+}
+
+//u128/u128
+impl FromStr for Interval {
+	type Err = IntervalParseError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if s.is_empty() {
+			return Err(IntervalParseError::Empty);
+		}
+		let (num, den) = s.split_once('/').ok_or(IntervalParseError::MissingSeparator)?;
+		let num = parse_u128(num.trim())?;
+		let den = parse_u128(den.trim())?;
+		if num == 0 || den == 0 {
+			return Err(IntervalParseError::Zero);
+		}
+		Ok(Self::new(num,den))
+	}
+}
+
+//greatest common divisor, euclidean algorithm
+pub fn gcd(mut a: u128, mut b: u128) -> u128 {
+	while b != 0 {
+		(a, b) = (b, a % b);
+	}
+	a
+}
+
+pub fn largest_prime_factor(n: u128) -> u128 {
+	factorize(n).last().copied().unwrap_or(1)
+}
+
+//the values that only need the prime factors of n and d together, as (prime, power) pairs
+//shared so a ratio that is already factored does not have to be factored again for each of them
+pub struct FactorMetrics {
+	pub prime_subgroup: Vec<u128>,
+	//None if they overflow
+	pub wilson_complexity: Option<u128>,
+	pub gradus_suavitatis: Option<u128>
+}
+
+impl FactorMetrics {
+	pub fn new(factors: &[(u128, u8)]) -> Self {
+		let mut prime_subgroup: Vec<u128> = factors.iter().map(|(p, _)| *p).collect();
+		prime_subgroup.sort();
+		Self{
+			prime_subgroup,
+			wilson_complexity: factors.iter().try_fold(0u128, |sum, (p, pow)| sum.checked_add(p.checked_mul(*pow as u128)?)),
+			gradus_suavitatis: factors.iter().try_fold(1u128, |sum, (p, pow)| sum.checked_add((p - 1).checked_mul(*pow as u128)?))
 		}
 	}
-	limit
 }
 
 pub struct PrimeFactors {
-	len: u8,
-	ar: [IntPower; 16]
+	len: u8,//u128 numbers can have at most 26 distinct prime factors
+	ar: [IntPower; 32]
 }
 
 impl PrimeFactors {
-	pub fn init(num: u64) -> Self {
+	pub fn init(num: u128) -> Self {
 		let mut x = Self::new();
 		x.set(num);
 		x
 	}
 	
 	fn new() -> Self {
-		Self{len: 0, ar: [IntPower::new(0,0); 16]}
+		Self{len: 0, ar: [IntPower::new(0,0); 32]}
 	}
 	
-	fn set(&mut self, mut num: u64) {
-		self.len = 1;
-		self.ar[0].num = 2;
-		self.ar[0].pow = 0;
-		
-		while self.ar[(self.len - 1) as usize].num <= num {
-			if num % self.ar[(self.len - 1) as usize].num == 0 {
-				num /= self.ar[(self.len - 1) as usize].num;
+	//false if a composite with prime factors too large to find is left in
+	pub fn is_complete(&self) -> bool {
+		self.iter().all(|(p, _)| is_prime(p))
+	}
+	
+	//(prime, power) pairs from smallest to largest prime
+	pub fn iter(&self) -> impl Iterator<Item = (u128, u8)> + '_ {
+		self.ar[..self.len as usize].iter().map(|p| (p.num, p.pow))
+	}
+	
+	fn set(&mut self, num: u128) {
+		self.len = 0;
+		for p in factorize(num) {
+			if self.len > 0 && self.ar[(self.len - 1) as usize].num == p {
 				self.ar[(self.len - 1) as usize].pow += 1;
-				
-			} else if self.ar[(self.len - 1) as usize].pow > 0 {
-				self.ar[self.len as usize].num = self.ar[(self.len - 1) as usize].num + 1;
-				self.ar[self.len as usize].pow = 0;
-				self.len += 1;
-				
 			} else {
-				self.ar[(self.len - 1) as usize].num += 1;
+				self.ar[self.len as usize] = IntPower::new(p, 1);
+				self.len += 1;
 			}
 		}
-		if self.ar[(self.len - 1) as usize].pow == 0 {
-			self.len -= 1;
-		}
 	}
-	
-	pub fn to_string(&self) -> String {
+}
+
+impl fmt::Display for PrimeFactors {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		if self.len == 0 {
-			return "1".to_string();
+			return write!(f, "1");
 		}
 		
-		let mut s = self.ar[0].to_string();
-		
-		for c in 1..self.len {
-			s += "*";
-			s += &self.ar[c as usize].to_string();
+		for c in 0..self.len {
+			let p = self.ar[c as usize];
+			if c > 0 {
+				write!(f, "*")?;
+			}
+			write!(f, "{}", p)?;
+			if !is_prime(p.num) {
+				write!(f, " (unfactored)")?;
+			}
 		}
 		
-		s
+		Ok(())
 	}
 }
 
 #[derive(Copy,Clone)]
 struct IntPower {
-	pub num: u64,
-	pub pow: u8//u64 numbers can only have max 64 pow
+	pub num: u128,
+	pub pow: u8//u128 numbers can only have max 127 pow
 }
 
 impl IntPower {
-	fn new(num: u64, pow: u8) -> Self {
+	fn new(num: u128, pow: u8) -> Self {
 		Self{num, pow}
 	}
 	
-	pub fn to_string(&self) -> String {
-		let mut s = self.num.to_string();
+}
+
+impl fmt::Display for IntPower {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.num)?;
 		if self.pow > 1 {
-			s += "^";
-			s += &self.pow.to_string();
+			write!(f, "^{}", self.pow)?;
 		}
-		s
+		Ok(())
 	}
 }
 
+//notes below the tonic are negative
 pub struct ScaleApproximation {
-	pub note: u64,
+	pub note: i64,
 	pub offset: f64
 }
 
 impl ScaleApproximation {
-	fn new(note: u64, offset: f64) -> Self {
+	fn new(note: i64, offset: f64) -> Self {
 		ScaleApproximation{note, offset}
 	}
-	
-	pub fn to_string(&self) -> String {
-		let plus = if self.offset > 0.0 { "+" } else { "" };
-		format!("{:<6}|  {}{}", self.note, plus, self.offset)
-	}
 }
 
-pub fn closest_edo_note(cents: f64, div: u64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
-	
-	loop {
-		let cents2 = edo_note_cents(div, note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
+impl fmt::Display for ScaleApproximation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let plus = if self.offset > 0.0 { "+" } else { "" };
+		write!(f, "{:<6}|  {}{}", self.note, plus, self.offset)
 	}
 }
 
-fn edo_note_cents(div: u64, note: u64) -> f64 {
-	((1200 * note) as f64) / (div as f64)
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScaleError {
+	NotFinite,
+	Empty,
+	InvalidPeriod,
+	//the note number does not fit in i64
+	OutOfRange
 }
 
-pub fn closest_bp_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
-	
-	loop {
-		let cents2 = bp_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
+impl fmt::Display for ScaleError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ScaleError::NotFinite => write!(f, "cents are not a finite number"),
+			ScaleError::Empty => write!(f, "scale has no notes"),
+			ScaleError::InvalidPeriod => write!(f, "scale period has to be larger than 0 cents"),
+			ScaleError::OutOfRange => write!(f, "too many periods away from the tonic"),
 		}
-		
-		note += 1;
-		offset = offset2;
 	}
 }
 
-fn bp_note_cents(note: u64) -> f64 {
-	(1901.9550008653873 * (note as f64)) / 13.0
+impl std::error::Error for ScaleError {}
+
+//a scale that repeats every period, all values in cents
+//steps are the notes within one period in ascending order, the first one should be the tonic at 0.0
+pub struct Scale {
+	pub period: f64,
+	pub steps: Vec<f64>,
+	//exact ratio of each step followed by the period, None or missing where only the cents are known
+	pub ratios: Vec<Option<Interval>>
 }
 
-pub fn closest_root_fifth_note(cents: f64, root: u64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
+impl Scale {
+	pub fn new(period: f64, steps: Vec<f64>) -> Self {
+		Self{period, steps, ratios: Vec::new()}
+	}
 	
-	loop {
-		let cents2 = root_fifth_note_cents(root, note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
+	pub fn from_ratios(period: &Interval, steps: &[Interval]) -> Self {
+		let mut scale = Self::new(period.cents(), steps.iter().map(|i| i.cents()).collect());
+		scale.ratios = steps.iter().chain([period]).map(|i| Some(*i)).collect();
+		scale
 	}
-}
-
-fn root_fifth_note_cents(root: u64, note: u64) -> f64 {
-	1.5f64.powf((note as f64)/(root as f64)).log2() * 1200.0//maybe refactor
-}
-
-pub fn closest_just_stack_note(cents: f64, interval: &Interval, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
 	
-	loop {
-		//let cents2 = just_stack_note_cents(interval, note);//change back to this one when it no longer integer overflows
-		let cents2 = just_stack_note_cents_simple(interval, note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
+	//period divided into div equal steps
+	pub fn equal(period: f64, div: u64) -> Self {
+		Self::new(period, (0..div).map(|note| (period * note as f64) / div as f64).collect())
 	}
-}
-
-/*
-//TODO check if integer will overflow, and use the other one if so
-fn just_stack_note_cents(i: &Interval, note: u64) -> f64 {
-	Interval::new(
-		(i.num as u128).pow(note as u32) as u64,
-		(i.den as u128).pow(note as u32) as u64
-	).cents()
-}
-*/
-
-//unlike the other one, will not integer overflow
-fn just_stack_note_cents_simple(i: &Interval, note: u64) -> f64 {
-	i.cents() * note as f64
-}
-
-pub fn closest_tritave_19_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
 	
-	loop {
-		let cents2 = tritave_19_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
+	//equal divisions of a just period, which is kept as a ratio for exporting
+	pub fn equal_ratio(period: &Interval, div: u64) -> Self {
+		let mut scale = Self::equal(period.cents(), div);
+		scale.ratios = (0..div).map(|note| (note == 0).then(|| Interval::new(1,1))).chain([Some(*period)]).collect();
+		scale
 	}
-}
-
-fn tritave_19_note_cents(note: u64) -> f64 {
-	(1901.9550008653873 * (note as f64)) / 19.0
-}
-
-pub fn closest_just_major_scale_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
 	
-	loop {
-		let cents2 = just_major_scale_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
+	pub fn edo(div: u64) -> Self {
+		Self::equal_ratio(&Interval::new(2,1), div)
+	}
+	
+	//Bohlen Pierce, 13 equal divisions of the tritave
+	pub fn bp() -> Self {
+		Self::equal_ratio(&Interval::new(3,1), 13)
+	}
+	
+	pub fn tritave_19() -> Self {
+		Self::equal_ratio(&Interval::new(3,1), 19)
+	}
+	
+	//root-th root of the perfect fifth, e.g. 9 gives the alpha scale
+	pub fn root_fifth(root: u64) -> Self {
+		Self::equal_ratio(&Interval::new(3,2), root)
+	}
+	
+	//the interval stacked on top of itself
+	pub fn just_stack(interval: &Interval) -> Self {
+		Self::from_ratios(interval, &[Interval::new(1,1)])
 	}
-}
-
-fn just_major_scale_note_cents(note: u64) -> f64 {
-	let scale: [f64;7] = [
-		0.0,//					1/1
-		203.91000173077484,//	9/8
-		386.3137138648348,//	5/4
-		498.0449991346125,//	4/3
-		701.9550008653874,//	3/2
-		884.3587129994474,//	5/3
-		1088.2687147302222,//	15/8
-	];
-	scale[(note % 7) as usize] + ((note / 7) * 1200) as f64
-}
-
-pub fn closest_just_minor_scale_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
 	
-	loop {
-		let cents2 = just_minor_scale_note_cents(note);
-		let offset2 = cents2 - cents;
+	pub fn just_major() -> Self {
+		Self::from_ratios(&Interval::new(2,1), &[
+			Interval::new(1,1),
+			Interval::new(9,8),
+			Interval::new(5,4),
+			Interval::new(4,3),
+			Interval::new(3,2),
+			Interval::new(5,3),
+			Interval::new(15,8),
+		])
+	}
+	
+	pub fn just_minor() -> Self {
+		Self::from_ratios(&Interval::new(2,1), &[
+			Interval::new(1,1),
+			Interval::new(16,15),
+			Interval::new(6,5),
+			Interval::new(4,3),
+			Interval::new(3,2),
+			Interval::new(8,5),
+			Interval::new(9,5),
+		])
+	}
+	
+	pub fn just_chromatic() -> Self {
+		Self::from_ratios(&Interval::new(2,1), &[
+			Interval::new(1,1),
+			Interval::new(16,15),
+			Interval::new(9,8),
+			Interval::new(6,5),
+			Interval::new(5,4),
+			Interval::new(4,3),
+			Interval::new(45,32),//maybe use a different tritone? 7/5?
+			Interval::new(3,2),
+			Interval::new(8,5),
+			Interval::new(5,3),
+			Interval::new(9,5),
+			Interval::new(15,8),
+		])
+	}
+	
+	pub fn just_harmonic_12() -> Self {
+		Self::from_ratios(&Interval::new(2,1), &[
+			Interval::new(1,1),
+			Interval::new(17,16),
+			Interval::new(9,8),
+			Interval::new(19,16),
+			Interval::new(5,4),
+			Interval::new(21,16),
+			Interval::new(11,8),
+			Interval::new(3,2),
+			Interval::new(13,8),
+			Interval::new(27,16),
+			Interval::new(7,4),
+			Interval::new(15,8),
+		])
+	}
+	
+	pub fn just_harmonic_16() -> Self {
+		Self::from_ratios(&Interval::new(2,1), &[
+			Interval::new(1,1),
+			Interval::new(17,16),
+			Interval::new(9,8),
+			Interval::new(19,16),
+			Interval::new(5,4),
+			Interval::new(21,16),
+			Interval::new(11,8),
+			Interval::new(23,16),
+			Interval::new(3,2),
+			Interval::new(25,16),
+			Interval::new(13,8),
+			Interval::new(27,16),
+			Interval::new(7,4),
+			Interval::new(29,16),
+			Interval::new(15,8),
+			Interval::new(31,16),
+		])
+	}
+	
+	pub fn just_bp() -> Self {
+		Self::from_ratios(&Interval::new(3,1), &[
+			Interval::new(1,1),
+			Interval::new(27,25),
+			Interval::new(25,21),
+			Interval::new(9,7),
+			Interval::new(7,5),
+			Interval::new(75,49),
+			Interval::new(5,3),
+			Interval::new(9,5),
+			Interval::new(49,25),
+			Interval::new(15,7),
+			Interval::new(7,3),
+			Interval::new(63,25),
+			Interval::new(25,9),
+		])
+	}
+	
+	pub fn pythagorean_19_tone_tritave() -> Self {
+		Self::from_ratios(&Interval::new(3,1), &[
+			Interval::new(1,1),//		1   / 1
+			Interval::new(256,243),//	2^8 / 3^5
+			Interval::new(9,8),//		3^2 / 2^3
+			Interval::new(32,27),//		2^5 / 3^3
+			Interval::new(81,64),//		3^4 / 2^6
+			Interval::new(4,3),//		2^2 / 3
+			Interval::new(729,512),//	3^6 / 2^9
+			Interval::new(3,2),//		3   / 2
+			Interval::new(128,81),//	2^7 / 3^4
+			Interval::new(27,16),//		3^3 / 2^4
+			Interval::new(16,9),//		2^4 / 3^2
+			Interval::new(243,128),//	3^5 / 2^7
+			Interval::new(2,1),//		2   / 1
+			Interval::new(512,243),//	2^9 / 3^5
+			Interval::new(9,4),//		3^2 / 2^2
+			Interval::new(64,27),//		2^6 / 3^3
+			Interval::new(81,32),//		3^4 / 2^5
+			Interval::new(8,3),//		2^3 / 3
+			Interval::new(729,256),//	3^6 / 2^8
+		])
+	}
+	
+	//exact ratio of the step within the period, the period itself is at index len
+	pub fn ratio(&self, step: usize) -> Option<&Interval> {
+		self.ratios.get(step).and_then(|r| r.as_ref())
+	}
+	
+	pub fn len(&self) -> usize {
+		self.steps.len()
+	}
+	
+	pub fn is_empty(&self) -> bool {
+		self.steps.is_empty()
+	}
+	
+	//note counted from the tonic at 0, going up through the periods, or down for negative notes
+	pub fn note_cents(&self, note: i64) -> f64 {
+		let len = self.len() as i64;
+		self.steps[note.rem_euclid(len) as usize] + note.div_euclid(len) as f64 * self.period
+	}
+	
+	//the period is found by division, and the note within it by binary search
+	pub fn closest_note(&self, cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+		if !cents.is_finite() {
+			return Err(ScaleError::NotFinite);
+		}
+		if self.is_empty() {
+			return Err(ScaleError::Empty);
+		}
+		if !(self.period > 0.0 && self.period.is_finite()) {
+			return Err(ScaleError::InvalidPeriod);
+		}
+		
+		let len = self.len() as i64;
+		let period = (cents / self.period).floor();
+		let within = cents - period * self.period;
+		let step = self.steps.partition_point(|s| *s <= within) as i64;
 		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
+		//notes on either side, the lower one can be in the previous period if the tonic is above 0.0
+		//far enough out the note number does not fit in i64
+		if period.abs() >= i64::MAX as f64 {
+			return Err(ScaleError::OutOfRange);
 		}
+		let lower = (period as i64).checked_mul(len)
+			.and_then(|n| n.checked_add(step - 1))
+			.filter(|n| n.checked_add(2).is_some())
+			.ok_or(ScaleError::OutOfRange)?;
+		let upper = lower + 1;
+		let lower_offset = self.note_cents(lower) - cents;
+		let upper_offset = self.note_cents(upper) - cents;
 		
-		note += 1;
-		offset = offset2;
+		//ties go to the upper note
+		let (note, offset) = if upper_offset.abs() <= lower_offset.abs() {
+			(upper, upper_offset)
+		} else {
+			(lower, lower_offset)
+		};
+		Ok(ScaleApproximation::new(note + !scale_start_0 as i64, offset))
 	}
 }
 
-fn just_minor_scale_note_cents(note: u64) -> f64 {
-	let scale: [f64;7] = [
-		0.0,//					1/1
-		111.73128526977774,//	16/15
-		315.64128700055255,//	6/5
-		498.0449991346125,//	4/3
-		701.9550008653874,//	3/2
-		813.6862861351652,//	8/5
-		1017.5962878659401,//	9/5
-	];
-	scale[(note % 7) as usize] + ((note / 7) * 1200) as f64
+pub fn closest_edo_note(cents: f64, div: u64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::edo(div).closest_note(cents, scale_start_0)
 }
 
-pub fn closest_just_chromatic_scale_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
-	
-	loop {
-		let cents2 = just_chromatic_scale_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
-	}
+pub fn closest_bp_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::bp().closest_note(cents, scale_start_0)
 }
 
-fn just_chromatic_scale_note_cents(note: u64) -> f64 {
-	let scale: [f64;12] = [
-		0.0,//					1/1
-		111.73128526977774,//	16/15
-		203.91000173077484,//	9/8
-		315.64128700055255,//	6/5
-		386.3137138648348,//	5/4
-		498.0449991346125,//	4/3
-		590.2237155956096,//	45/32, maybe use a different tritone? 7/5?
-		701.9550008653874,//	3/2
-		813.6862861351652,//	8/5
-		884.3587129994474,//	5/3
-		1017.5962878659401,//	9/5
-		1088.2687147302222,//	15/8
-	];
-	scale[(note % 12) as usize] + ((note / 12) * 1200) as f64
+pub fn closest_root_fifth_note(cents: f64, root: u64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::root_fifth(root).closest_note(cents, scale_start_0)
 }
 
-pub fn closest_just_harmonic_12_scale_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
-	
-	loop {
-		let cents2 = just_harmonic_12_scale_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
-	}
+pub fn closest_just_stack_note(cents: f64, interval: &Interval, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::just_stack(interval).closest_note(cents, scale_start_0)
 }
 
-fn just_harmonic_12_scale_note_cents(note: u64) -> f64 {
-	let scale: [f64;12] = [
-		0.0,//					1/1
-		104.95540950040728,//	17/16
-		203.91000173077484,//	9/8
-		297.5130161323026,//	19/16
-		386.3137138648348,//	5/4
-		470.7809073345124,//	21/16
-		551.3179423647567,//	11/8
-		701.9550008653874,//	3/2
-		840.5276617693106,//	13/8
-		905.8650025961623,//	27/16
-		968.8259064691249,//	7/4
-		1088.2687147302222,//	15/8
-	];
-	scale[(note % 12) as usize] + ((note / 12) * 1200) as f64
+pub fn closest_tritave_19_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::tritave_19().closest_note(cents, scale_start_0)
 }
 
-pub fn closest_just_harmonic_16_scale_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
-	
-	loop {
-		let cents2 = just_harmonic_16_scale_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
-	}
+pub fn closest_just_major_scale_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::just_major().closest_note(cents, scale_start_0)
 }
 
-fn just_harmonic_16_scale_note_cents(note: u64) -> f64 {
-	let scale: [f64;16] = [
-		0.0,//					1/1
-		104.95540950040728,//	17/16
-		203.91000173077484,//	9/8
-		297.5130161323026,//	19/16
-		386.3137138648348,//	5/4
-		470.7809073345124,//	21/16
-		551.3179423647567,//	11/8
-		628.2743472684155,//	23/16
-		701.9550008653874,//	3/2
-		772.6274277296696,//	25/16
-		840.5276617693106,//	13/8
-		905.8650025961623,//	27/16
-		968.8259064691249,//	7/4
-		1029.5771941530866,//	29/16
-		1088.2687147302222,//	15/8
-		1145.0355724642502,//	31/16
-	];
-	scale[(note % 16) as usize] + ((note / 16) * 1200) as f64
+pub fn closest_just_minor_scale_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::just_minor().closest_note(cents, scale_start_0)
 }
 
-pub fn closest_just_bp_scale_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
-	
-	loop {
-		let cents2 = just_bp_scale_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
-	}
+pub fn closest_just_chromatic_scale_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::just_chromatic().closest_note(cents, scale_start_0)
 }
 
-fn just_bp_scale_note_cents(note: u64) -> f64 {
-	let scale: [f64;13] = [
-		0.0,//					1/1
-		133.23757486649274,//	27/25
-		301.84652039515726,//	25/21
-		435.08409526165,//		9/7
-		582.51219260429,//		7/5
-		736.930615656807,//		75/49
-		884.3587129994474,//	5/3
-		1017.5962878659401,//	9/5
-		1165.0243852085803,//	49/25
-		1319.442808261097,//	15/7
-		1466.8709056037378,//	7/3
-		1600.10848047023,//		63/25
-		1768.7174259988947,//	25/9
-	];
-	scale[(note % 13) as usize] + ((note / 13) as f64 * 1901.9550008653873)
+pub fn closest_just_harmonic_12_scale_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::just_harmonic_12().closest_note(cents, scale_start_0)
 }
 
-pub fn closest_pythagorean_19_tone_tritave_scale_note(cents: f64, scale_start_0: bool) -> ScaleApproximation {
-	let mut note = 0;
-	let mut offset = f64::MAX;
-	
-	loop {
-		let cents2 = pythagorean_19_tone_tritave_scale_note_cents(note);
-		let offset2 = cents2 - cents;
-		
-		if offset.abs() < offset2.abs() {
-			return ScaleApproximation::new(note - scale_start_0 as u64, offset);
-		}
-		
-		note += 1;
-		offset = offset2;
-	}
+pub fn closest_just_harmonic_16_scale_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::just_harmonic_16().closest_note(cents, scale_start_0)
 }
 
-fn pythagorean_19_tone_tritave_scale_note_cents(note: u64) -> f64 {
-	let scale: [f64;19] = [
-		0.0,//					1/1			1   / 1
-		90.22499567306306,//	256/243		2^8 / 3^5
-		203.91000173077484,//	9/8			3^2 / 2^3
-		294.13499740383764,//	32/27		2^5 / 3^3
-		407.8200034615497,//	81/64		3^4 / 2^6
-		498.0449991346125,//	4/3			2^2 / 3
-		611.7300051923246,//	729/512		3^6 / 2^9
-		701.9550008653874,//	3/2			3   / 2
-		792.1799965384502,//	128/81		2^7 / 3^4
-		905.8650025961623,//	27/16		3^3 / 2^4
-		996.089998269225,//		16/9		2^4 / 3^2
-		1109.775004326937,//	243/128		3^5 / 2^7
-		1200.0,//				2/1     	2   / 1
-		1290.224995673063,//	512/243		2^9 / 3^5
-		1403.9100017307749,//	9/4			3^2 / 2^2
-		1494.1349974038376,//	64/27		2^6 / 2^3
-		1607.8200034615497,//	81/32		3^4 / 2^5
-		1698.0449991346125,//	8/3			2^3 / 3
-		1811.7300051923246,//	729/256		3^6 / 2^8
-	];
-	scale[(note % 19) as usize] + ((note / 19) as f64 * 1901.9550008653873)
+pub fn closest_just_bp_scale_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::just_bp().closest_note(cents, scale_start_0)
 }
 
-//TODO Refactor find_closest_note and the just_scales_note to use common code. Maybe make a struct that describes a scale
+pub fn closest_pythagorean_19_tone_tritave_scale_note(cents: f64, scale_start_0: bool) -> Result<ScaleApproximation, ScaleError> {
+	Scale::pythagorean_19_tone_tritave().closest_note(cents, scale_start_0)
+}
 
 //-----------------------
 
@@ -543,18 +783,17 @@ struct IntervalNamePair {
 }
 
 //shorter
-const fn i(num: u64, den: u64, name: &'static str) -> IntervalNamePair {
+const fn i(num: u128, den: u128, name: &'static str) -> IntervalNamePair {
 	IntervalNamePair{interval: Interval{num,den}, name}
 }
 
 //maybe add some intervals and names from other sources
-const NAMES: [IntervalNamePair;560] = [
+static NAMES: [IntervalNamePair;562] = [
 	
 	i(3, 1, "tritave, perfect twelfth, BP thirteenth"),
 	i(4, 1, "double octave"),
 	
 	//https://www.huygens-fokker.org/docs/intervals.html
-	//commented out last 2 intervals, because their numerators and/or denominators don't fit in u64
 	i(1, 1, "unison, perfect prime"),
 	i(2, 1, "octave"),
 	i(3, 2, "perfect fifth"),
@@ -1113,7 +1352,7 @@ const NAMES: [IntervalNamePair;560] = [
 	i(7629394531250, 7625597484987, "ennealimmal comma"),
 	i(19073486328125, 19042491875328, "'19-tone' comma"),
 	i(450359962737049600, 450283905890997363, "monzisma"),
-	//i(36893488147419103232, 36472996377170786403, "'41-tone' comma"),
-	//i(19383245667680019896796723, 19342813113834066795298816, "Mercator's comma"),
+	i(36893488147419103232, 36472996377170786403, "'41-tone' comma"),
+	i(19383245667680019896796723, 19342813113834066795298816, "Mercator's comma"),
 ];
 
@@ -1,13 +1,9 @@
-use std::{cmp, env};
+use std::{env, io};
 use interval_info::*;
 
-//TODO add flags to suppress some output, or only show specific output
-//TODO option to pass interval as decimal
-//TODO passing interval as cents might not make sense, but think about it.
-
 fn main() {
 
-	let auto_flip = true;
+	let mut auto_flip = true;
 	
 	let mut scale_start_0 = true;
 	
@@ -22,13 +18,50 @@ fn main() {
 		println!(
 			"\
 help:
- last argument is the interval to input
- u64/u64
- e.g. 3/2
+ intervals to analyze, one report each, - reads them from stdin, one per line
+ chords are analyzed as chords, written as 4:5:6:7 or as ratios above the root like 5/4,3/2
+ u128/u128         | ratio           | e.g. 3/2
+ decimal           | exact ratio     | e.g. 1.25
+ cents             | ends in c       | e.g. 701.955c
+ monzo             | prime exponents | e.g. [-1 1>
+ steps\\divisions   | EDO steps       | e.g. 7\\12, or 7\\13<3> for other periods
+ pitch^exponent    | power           | e.g. 3^(1/9), (3/2)^2
 
 options:
  -h --help          | flag | display this help message
- -1 --scale-start-1 | flag | start scale tonic note count at 1 instead of 0"
+ -1 --scale-start-1 | flag | start scale tonic note count at 1 instead of 0
+ --no-flip          | flag | keep intervals below 1/1 as descending intervals with negative notes
+ --scale <file.scl> | path | also approximate with a Scala scale file, can be repeated
+ --max-den <n>      | u128 | largest denominator of the nearest just intervals, default 1000
+ --prime-limit <n>  | u128 | only show nearest just intervals within this prime limit
+ --format <f>       | text | report format, text or json, default text
+ --only <s,s,..>    | list | only show these sections of the report
+ --hide <s,s,..>    | list | leave these sections out of the report
+ --no-approximations| flag | leave out all of the scale approximations
+ --edos <d,d,..>    | list | approximate with these EDOs instead of the built-in ones
+ --base <f>         | f64  | frequency of 1/1 in Hz or as a note like A4, adds frequency, MIDI note and beats
+ --bend-range <n>   | f64  | pitch bend range in semitones for the MIDI pitch bend value, default 2
+ --render <file.wav>| path | write the interval and then its closest note in a scale to a WAV file
+ --render-scale <s> | name | scale to approximate with when rendering, like --export, default edo:12
+ --timbre <t>       | name | sine, saw or harmonic, default sine when rendering and harmonic for dissonance
+ --dissonance-curve <min-max> | print the dissonance curve from min to max cents as CSV at --base, then exit
+ --step <cents>               | spacing of the dissonance or entropy curve, default 1
+ --entropy-set <s>  | name | ratios of the harmonic entropy, farey or tenney, default tenney
+ --entropy-limit <n>| u128 | largest n and d of farey, or n*d of tenney ratios, default 100 or 10000
+ --entropy-spread <c>| f64 | standard deviation of the hearing of an interval in cents, default 17
+ --entropy-curve <min-max>    | print the harmonic entropy from min to max cents as CSV, then exit
+ --arrangement <a>  | name | dyad, arpeggio or both, default both
+ --table            | flag | compare the intervals in one table, with 12, 31 and 53 EDO unless --edos
+   sections: cents, frequency, beats, ratio, limits, factors, complexity, entropy, names, combination, just, edo,
+             non-octave, non-tet, approximations (edo, non-octave and non-tet)
+ --best-edo <i,i,..>     | rank EDOs by how well they approximate all of the intervals, then exit
+ --edo-range <min-max>   | EDOs to rank or to search for chords, default 1-100
+ --top <n>               | number of EDOs to show, default 10
+ --relative              | rank by error relative to the step size instead of cents
+ --tolerance <cents>     | largest error of any dyad in the EDOs shown for a chord, default 10
+ --export <scale> <path> | write a built-in scale to <path>.scl and <path>.kbm, then exit
+   scales: just-major, just-minor, just-chromatic, pythagorean-19, harmonic-12, harmonic-16,
+           just-bp, bp, tritave-19, edo:<div>, root-fifth:<root>, stack:<u128/u128>"
 		);
 		return;
 	}
@@ -37,178 +70,808 @@ options:
 		scale_start_0 = false;
 	}
 	
-	let mut i = Interval::from_str(&args[args.len() -1]);
+	if args.contains(&"--no-flip".to_string()) {
+		auto_flip = false;
+	}
 	
-	let common_factor = i.reduce();
-	if common_factor > 1 {
-		println!("Interval reduced by common factor: {}\n", common_factor);
+	if let Some(n) = args.iter().position(|a| a == "--export") {
+		let (Some(name), Some(path)) = (args.get(n + 1), args.get(n + 2)) else {
+			println!("--export needs a scale name and an output path");
+			return;
+		};
+		let Some(scl) = builtin_scale(name) else {
+			println!("Unknown scale: {}", name);
+			return;
+		};
+		let kbm = KeyboardMapping::linear(&scl.scale);
+		let scl_path = format!("{}.scl", path);
+		let kbm_path = format!("{}.kbm", path);
+		if let Err(e) = scl.write(&scl_path).and_then(|_| kbm.write(&kbm_path)) {
+			println!("Could not export scale: {}", e);
+			return;
+		}
+		println!("Exported {} to {} and {}", scl.description, scl_path, kbm_path);
+		return;
 	}
 	
-	if auto_flip && i.den > i.num {
-		println!("Interval flipped! Original: {}/{}\n", i.num, i.den);
-		(i.num, i.den) = (i.den, i.num);
+	let range = option_value(&args, "--edo-range").map(|r| r.as_str()).unwrap_or("1-100");
+	let Some((min_div, max_div)) = range.split_once('-')
+		.and_then(|(min, max)| Some((min.parse::<u64>().ok()?, max.parse::<u64>().ok()?))) else {
+		println!("--edo-range should be written like 1-100");
+		return;
+	};
+	
+	let top = match option_value(&args, "--top").map(|v| v.parse::<usize>()) {
+		None => 10,
+		Some(Ok(n)) => n,
+		Some(Err(_)) => {
+			println!("--top needs a whole number");
+			return;
+		}
+	};
+	
+	if let Some(list) = option_value(&args, "--best-edo") {
+		let mut intervals = Vec::new();
+		for s in list.split(',') {
+			match s.parse::<Pitch>() {
+				Ok(p) => intervals.push((s, p.cents())),
+				Err(e) => {
+					println!("Could not read interval {}: {}", s, e);
+					return;
+				}
+			}
+		}
+		
+		let relative = args.contains(&"--relative".to_string());
+		print_best_edos(&intervals, min_div, max_div, top, relative);
+		return;
 	}
 	
-	let num_limit = largest_prime_factor(i.num);
-	let den_limit = largest_prime_factor(i.den);
-	let limit = cmp::max(num_limit, den_limit);
+	let mut scales = Vec::new();
+	for (n, arg) in args.iter().enumerate() {
+		if arg != "--scale" {
+			continue;
+		}
+		let Some(path) = args.get(n + 1) else {
+			println!("--scale needs a path to a .scl file");
+			return;
+		};
+		match ScalaFile::read(path) {
+			Ok(scl) => {
+				let label = if scl.description.is_empty() { path } else { &scl.description };
+				scales.push((label.chars().take(27).collect(), scl.scale));
+			}
+			Err(e) => {
+				println!("Could not load scale {}: {}", path, e);
+				return;
+			}
+		}
+	}
 	
-	let num_primes = PrimeFactors::init(i.num);
-	let den_primes = PrimeFactors::init(i.den);
+	let max_den = match option_value(&args, "--max-den").map(|v| v.parse::<u128>()) {
+		None => 1000,
+		Some(Ok(n)) if n > 0 => n,
+		_ => {
+			println!("--max-den needs a positive whole number");
+			return;
+		}
+	};
 	
-	let cents = i.cents();
+	let prime_limit = match option_value(&args, "--prime-limit").map(|v| v.parse::<u128>()) {
+		None => None,
+		Some(Ok(n)) => Some(n),
+		Some(Err(_)) => {
+			println!("--prime-limit needs a whole number");
+			return;
+		}
+	};
 	
-	println!(
+	let json = match option_value(&args, "--format").map(|f| f.as_str()) {
+		None | Some("text") => false,
+		Some("json") => true,
+		Some(f) => {
+			println!("Unknown format: {}, use text or json", f);
+			return;
+		}
+	};
+	
+	let table = args.contains(&"--table".to_string());
+	
+	let mut sections = match option_value(&args, "--only").map(|list| ReportSection::parse_list(list)) {
+		None => ReportSection::ALL.to_vec(),
+		Some(Ok(sections)) => sections,
+		Some(Err(e)) => {
+			println!("{}", e);
+			return;
+		}
+	};
+	match option_value(&args, "--hide").map(|list| ReportSection::parse_list(list)) {
+		None => {}
+		Some(Ok(hidden)) => sections.retain(|s| !hidden.contains(s)),
+		Some(Err(e)) => {
+			println!("{}", e);
+			return;
+		}
+	}
+	if args.contains(&"--no-approximations".to_string()) {
+		sections.retain(|s| !ReportSection::APPROXIMATIONS.contains(s));
+	}
+	if table {
+		sections.retain(|s| TABLE_SECTIONS.contains(s));
+	}
+	
+	let edos = match option_value(&args, "--edos") {
+		None if table => Some(vec![12, 31, 53]),
+		None => None,
+		Some(list) => match list.split(',').map(|d| d.trim().parse::<u64>().ok().filter(|d| *d > 0)).collect::<Option<Vec<u64>>>() {
+			Some(divs) => Some(divs),
+			None => {
+				println!("--edos needs a list of positive whole numbers, e.g. 12,31,53");
+				return;
+			}
+		}
+	};
+	
+	let base = match option_value(&args, "--base").map(|b| b.parse::<Frequency>()) {
+		None => None,
+		Some(Ok(f)) => Some(f),
+		Some(Err(e)) => {
+			println!("--base: {}", e);
+			return;
+		}
+	};
+	
+	let bend_range = match option_value(&args, "--bend-range").map(|v| v.parse::<f64>()) {
+		None => 2.0,
+		Some(Ok(r)) if r > 0.0 => r,
+		_ => {
+			println!("--bend-range needs a positive number of semitones");
+			return;
+		}
+	};
+	
+	let timbre = match option_value(&args, "--timbre").map(|t| t.parse::<Timbre>()) {
+		None => None,
+		Some(Ok(t)) => Some(t),
+		Some(Err(e)) => {
+			println!("{}", e);
+			return;
+		}
+	};
+	
+	let entropy_set = match option_value(&args, "--entropy-set").map(|s| s.parse::<RatioSet>()) {
+		None => RatioSet::Tenney,
+		Some(Ok(s)) => s,
+		Some(Err(e)) => {
+			println!("{}", e);
+			return;
+		}
+	};
+	
+	let entropy_limit = match option_value(&args, "--entropy-limit").map(|v| v.parse::<u128>()) {
+		None => entropy_set.default_limit(),
+		Some(Ok(l)) if (1..=entropy_set.max_limit()).contains(&l) => l,
+		_ => {
+			println!("--entropy-limit needs a whole number from 1 to {} for the {} set", entropy_set.max_limit(), entropy_set);
+			return;
+		}
+	};
+	
+	let entropy_spread = match option_value(&args, "--entropy-spread").map(|v| v.parse::<f64>()) {
+		None => 17.0,
+		Some(Ok(s)) if s > 0.0 && s.is_finite() => s,
+		_ => {
+			println!("--entropy-spread needs a positive number of cents");
+			return;
+		}
+	};
+	
+	let step = match option_value(&args, "--step").map(|v| v.parse::<f64>()) {
+		None => 1.0,
+		Some(Ok(s)) if s > 0.0 && s.is_finite() => s,
+		_ => {
+			println!("--step needs a positive number of cents");
+			return;
+		}
+	};
+	
+	if let Some(range) = option_value(&args, "--dissonance-curve") {
+		let Some((min, max)) = parse_cents_range(range) else {
+			println!("--dissonance-curve should be written like 0-1200");
+			return;
+		};
+		let base = base.unwrap_or(Frequency(220.0));
+		let partials = timbre.unwrap_or(Timbre::Harmonic).partials();
+		let points = dissonance_curve(base.0, &partials, min, max, step, edos.as_deref().unwrap_or(&[12]));
+		print!("{}", dissonance_csv(&points));
+		return;
+	}
+	
+	if let Some(range) = option_value(&args, "--entropy-curve") {
+		let Some((min, max)) = parse_cents_range(range) else {
+			println!("--entropy-curve should be written like 0-1200");
+			return;
+		};
+		let entropy = HarmonicEntropy::new(entropy_set, entropy_limit, entropy_spread);
+		print!("{}", entropy_csv(&entropy.curve(min, max, step)));
+		return;
+	}
+	
+	let mut inputs = Vec::new();
+	for arg in positional_args(&args) {
+		if arg != "-" {
+			inputs.push(arg.clone());
+			continue;
+		}
+		for line in io::stdin().lines() {
+			match line {
+				Ok(line) if !line.trim().is_empty() => inputs.push(line.trim().to_string()),
+				Ok(_) => {}
+				Err(e) => {
+					println!("Could not read from stdin: {}", e);
+					return;
+				}
+			}
+		}
+	}
+	if inputs.is_empty() {
+		println!("No interval passed in.\nPass in a fraction.\ne.g. 3/2");
+		return;
+	}
+	
+	let options = ReportOptions{
+		auto_flip,
+		scale_start_0,
+		max_den,
+		prime_limit,
+		scales,
+		sections,
+		edos,
+		base,
+		bend_range,
+		timbre: timbre.unwrap_or(Timbre::Harmonic),
+		entropy_set,
+		entropy_limit,
+		entropy_spread
+	};
+	let tolerance = match option_value(&args, "--tolerance").map(|v| v.parse::<f64>()) {
+		None => 10.0,
+		Some(Ok(t)) if t >= 0.0 => t,
+		_ => {
+			println!("--tolerance needs a number of cents");
+			return;
+		}
+	};
+	
+	let analyses: Vec<Result<Analysis, String>> = inputs.iter().map(|input| {
+		if Chord::is_chord(input) {
+			let chord = input.parse::<Chord>().map_err(|e| format!("Could not read chord {}: {}", input, e))?;
+			ChordReport::new(input, chord, min_div, max_div, tolerance).map(Analysis::Chord).map_err(|e| e.to_string())
+		} else {
+			let pitch = input.parse::<Pitch>().map_err(|e| format!("Could not read interval {}: {}", input, e))?;
+			Ok(Analysis::Interval(Box::new(Report::new(input, pitch, &options))))
+		}
+	}).collect();
+	
+	let rendered = match option_value(&args, "--render") {
+		None => None,
+		Some(path) => match render(&args, path, &analyses, base, timbre) {
+			Ok(message) => Some(message),
+			Err(e) => {
+				println!("Could not render {}: {}", path, e);
+				return;
+			}
+		}
+	};
+	
+	if json {
+		let mut values: Vec<Json> = analyses.iter().zip(&inputs).map(|(analysis, input)| match analysis {
+			Ok(Analysis::Interval(report)) => report.to_json(),
+			Ok(Analysis::Chord(report)) => report.to_json(),
+			Err(e) => Json::object().with("input", input.as_str()).with("error", e.as_str()),
+		}).collect();
+		if values.len() == 1 {
+			println!("{:#}", values.remove(0));
+		} else {
+			println!("{:#}", Json::Array(values));
+		}
+		return;
+	}
+	
+	if table {
+		let scales: Vec<&str> = options.scales.iter().map(|(label, _)| label.as_str()).collect();
+		print_table(&analyses, options.edos.as_deref().unwrap_or_default(), &scales);
+		return;
+	}
+	
+	let texts: Vec<String> = analyses.iter().map(|analysis| match analysis {
+		Ok(Analysis::Interval(report)) => report_text(report).join("\n\n"),
+		Ok(Analysis::Chord(report)) => chord_text(report, top).join("\n\n"),
+		Err(e) => e.clone(),
+	}).collect();
+	println!("{}", texts.join(&format!("\n\n{}\n\n", "=".repeat(60))));
+	if let Some(message) = rendered {
+		println!("\n{}", message);
+	}
+}
+
+//writes the interval and its closest note in a scale to a WAV file, returns what was rendered
+fn render(args: &[String], path: &str, analyses: &[Result<Analysis, String>], base: Option<Frequency>, timbre: Option<Timbre>) -> Result<String, String> {
+	let report = match analyses {
+		[Ok(Analysis::Interval(report))] => report,
+		[Err(e)] => return Err(e.clone()),
+		_ => return Err("rendering needs exactly one interval".to_string()),
+	};
+	
+	let name = option_value(args, "--render-scale").map(|s| s.as_str()).unwrap_or("edo:12");
+	let scl = builtin_scale(name).ok_or(format!("unknown scale {}", name))?;
+	let arrangement = option_value(args, "--arrangement").map_or(Ok(Arrangement::Both), |a| a.parse::<Arrangement>()).map_err(|e| e.to_string())?;
+	let base = base.unwrap_or(Frequency(220.0));
+	let timbre = timbre.unwrap_or(Timbre::Sine);
+	
+	let cents = report.pitch.cents();
+	let approximation = scl.scale.closest_note(cents, true).map_err(|e| e.to_string())?;
+	let wav = render_comparison(base.0, cents, cents + approximation.offset, timbre, arrangement);
+	wav.write(path).map_err(|e| e.to_string())?;
+	
+	Ok(format!(
+		"Rendered {} above {}, then note {} of {} ({:+} cents), to {} ({} seconds)",
+		report.input,
+		base,
+		approximation.note,
+		scl.description,
+		approximation.offset,
+		path,
+		wav.seconds()
+	))
+}
+
+enum Analysis {
+	Interval(Box<Report>),
+	Chord(ChordReport)
+}
+
+fn chord_text(report: &ChordReport, top: usize) -> Vec<String> {
+	let chord = &report.chord;
+	let utonal = match &report.utonal {
+		Some(u) => {
+			let u: Vec<String> = u.iter().map(|u| u.to_string()).collect();
+			format!("1/({})", u.join(":"))
+		}
+		None => "too large for u128".to_string(),
+	};
+	let cents: Vec<String> = chord.cents().iter().map(|c| format!("{:.3}", c)).collect();
+	
+	let mut blocks = vec![format!(
 		"\
-Interval                   : {}/{}
-Decimal                    : {}
-Octaves                    : {}
-Tritaves                   : {}
-Cents                      : {}
+Chord                      : {}
+Otonal                     : {}
+Utonal                     : {}
+Simplest Form              : {}
+Prime Limit                : {}
+Cents Above Root           : {}",
+		report.input,
+		chord,
+		utonal,
+		if chord.is_utonal() { "utonal" } else { "otonal" },
+		chord.prime_limit(),
+		cents.join(", "),
+	)];
+	
+	let mut dyads = vec!["Dyads:\nHarmonics                  : ratio      |  cents               |  name".to_string()];
+	for d in &report.dyads {
+		dyads.push(format!(
+			"{:<27}: {:<11}|  {:<20}|  {}",
+			format!("{}:{}", d.lower, d.upper),
+			d.interval.to_string(),
+			d.interval.cents(),
+			d.interval.get_name()
+		));
+	}
+	blocks.push(dyads.join("\n"));
+	
+	let mut edos = vec![format!(
+		"EDOs with every dyad within {} cents:\nEDO   : max error              |  steps of each note",
+		report.tolerance
+	)];
+	for e in report.edos.iter().take(top) {
+		let notes: Vec<String> = e.notes.iter().map(|n| n.to_string()).collect();
+		edos.push(format!("{:<6}: {:<23}|  {}", e.div, e.max_error, notes.join(", ")));
+	}
+	if report.edos.is_empty() {
+		edos.push("none in the EDO range".to_string());
+	}
+	blocks.push(edos.join("\n"));
+	
+	blocks
+}
 
-Numerator                  : {}
-Denominator                : {}
+//sections shown in the comparison table
+const TABLE_SECTIONS: [ReportSection; 4] = [ReportSection::Cents, ReportSection::Limits, ReportSection::Names, ReportSection::Edo];
 
-Tuning Limit               : {}
-Numerator Tuning Limit     : {}
-Denominator Tuning Limit   : {}
+//one row per interval, with the cents off of the closest note of each EDO
+//scales are the labels of the --scale files, which come after the EDOs
+fn print_table(analyses: &[Result<Analysis, String>], edos: &[u64], scales: &[&str]) {
+	let mut header = format!("{:<16}| {:<11}| {:<6}", "Interval", "Cents", "Limit");
+	let columns: Vec<String> = edos.iter().map(|div| format!("{}-EDO", div)).chain(scales.iter().map(|s| s.to_string())).collect();
+	let widths: Vec<usize> = columns.iter().map(|c| (c.chars().count() + 1).max(9)).collect();
+	for (column, width) in columns.iter().zip(&widths) {
+		header.push_str(&format!("| {:<width$}", column));
+	}
+	println!("{}|  Name(s)", header);
+	
+	for analysis in analyses {
+		let report = match analysis {
+			Ok(Analysis::Interval(report)) => report,
+			Ok(Analysis::Chord(report)) => {
+				println!("{:<16}| chord, leave out --table to analyze it", report.chord.to_string());
+				continue;
+			}
+			Err(e) => {
+				println!("{}", e);
+				continue;
+			}
+		};
+		let (interval, limit, name) = match (&report.pitch, &report.ratio) {
+			(Pitch::Ratio(i), Some(r)) => (i.to_string(), r.factors.as_ref().map_or("-".to_string(), |f| f.limit.to_string()), r.name.as_str()),
+			_ => (report.interval(), "-".to_string(), ""),
+		};
+		let mut row = format!("{:<16}| {:<11.3}| {:<6}", interval, report.pitch.cents(), limit);
+		let rows = report.approximations.iter().flat_map(|section| section.groups.iter().flatten());
+		for (a, width) in rows.zip(&widths) {
+			let offset = match &a.approximation {
+				Ok(a) => format!("{:+.3}", a.offset),
+				Err(_) => "-".to_string(),
+			};
+			row.push_str(&format!("| {:<width$}", offset));
+		}
+		println!("{}", format!("{}|  {}", row, name).trim_end());
+	}
+}
 
-Numerator Prime Factors    : {}
-Denominator Primes Factors : {}
+//arguments that are not options or the values of options
+//a negative number like -500c is an interval, not an option
+fn positional_args(args: &[String]) -> Vec<&String> {
+	let mut positional = Vec::new();
+	let mut n = 0;
+	while n < args.len() {
+		let arg = &args[n];
+		if let Some(count) = VALUE_OPTIONS.iter().find(|(o, _)| o == arg).map(|(_, count)| count) {
+			n += count + 1;
+			continue;
+		}
+		if FLAGS.contains(&arg.as_str()) {
+			n += 1;
+			continue;
+		}
+		let number = arg.strip_prefix('-').is_some_and(|a| a.starts_with(|c: char| c.is_ascii_digit() || c == '.'));
+		if arg == "-" || number || !arg.starts_with('-') {
+			positional.push(arg);
+		}
+		n += 1;
+	}
+	positional
+}
 
-Name(s) : {}
-
-Equal Temperament Approximations:
-Scale / EDO / TET          : note  |  cents off
-12 standard semi tone scale: {}
-24 quarter tone scale      : {}
-19 ~ 1/3 comma meantone ext: {}
-31 ~ 1/4 comma meantone ext: {}
-41                         : {}
-53                         : {}
-
-1 octave scale             : {}
-2 12tet tritone scale      : {}
-3 12tet major third scale  : {}
-4 12tet minor third scale  : {}
-
-5                          : {}
-6 whole tone scale         : {}
-7                          : {}
-8 neutral second, 3/2 tone : {}
-9 2/3 tone scale           : {}
-10 5*2                     : {}
-
-15 5*3                     : {}
-17                         : {}
-22                         : {}
-23                         : {}
-26                         : {}
-27 9*3                     : {}
-29                         : {}
-34 17*2                    : {}
-72 sixth tone scale        : {}
-96 eighth tone scale       : {}
-
-Non Octave TET Scales:
-Bohlen Pierce (TET version): {}
-3/1 div 19                 : {}
-
-Alpha (3/2)^(1/9)  ~78.0c  : {}
-Beta  (3/2)^(1/11) ~63.8c  : {}
-Gamma (3/2)^(1/20) ~35.1c  : {}
-
-16/15 just semitone scale  : {}
-10/9 just minor whole tone : {}
-9/8 just major whole tone  : {}
-8/7 septimal whole tone    : {}
-7/6 septimal minor third   : {}
-6/5 just minor third scale : {}
-5/4 just major third scale : {}
-4/3 just perfect fourth    : {}
-3/2 just perfect fifth     : {}
-
-Non TET Scales:
-Just major scale           : {}
-Just minor scale           : {}
-Just chromatic scale       : {}
-Pythagorean scale          : {}
-Harmonic 12 tone scale     : {}
-Harmonic 16 tone scale     : {}
-Just Bohlen Pierce scale   : {}",
-		
-		i.num,
-		i.den,
-		i.to_f64(),
-		i.octaves(),
-		i.tritaves(),
-		cents,
-		
-		i.num,
-		i.den,
-		
-		limit,
-		num_limit,
-		den_limit,
-		
-		num_primes.to_string(),
-		den_primes.to_string(),
-		
-		i.get_name(),
-		
-		closest_edo_note(cents, 12, scale_start_0).to_string(),
-		closest_edo_note(cents, 24, scale_start_0).to_string(),
-		closest_edo_note(cents, 19, scale_start_0).to_string(),
-		closest_edo_note(cents, 31, scale_start_0).to_string(),
-		closest_edo_note(cents, 41, scale_start_0).to_string(),
-		closest_edo_note(cents, 53, scale_start_0).to_string(),
-		
-		closest_edo_note(cents, 1, scale_start_0).to_string(),
-		closest_edo_note(cents, 2, scale_start_0).to_string(),
-		closest_edo_note(cents, 3, scale_start_0).to_string(),
-		closest_edo_note(cents, 4, scale_start_0).to_string(),
-		
-		closest_edo_note(cents, 5, scale_start_0).to_string(),
-		closest_edo_note(cents, 6, scale_start_0).to_string(),
-		closest_edo_note(cents, 7, scale_start_0).to_string(),
-		closest_edo_note(cents, 8, scale_start_0).to_string(),
-		closest_edo_note(cents, 9, scale_start_0).to_string(),
-		closest_edo_note(cents, 10, scale_start_0).to_string(),
-		
-		closest_edo_note(cents, 15, scale_start_0).to_string(),
-		closest_edo_note(cents, 17, scale_start_0).to_string(),
-		closest_edo_note(cents, 22, scale_start_0).to_string(),
-		closest_edo_note(cents, 23, scale_start_0).to_string(),
-		closest_edo_note(cents, 26, scale_start_0).to_string(),
-		closest_edo_note(cents, 27, scale_start_0).to_string(),
-		closest_edo_note(cents, 29, scale_start_0).to_string(),
-		closest_edo_note(cents, 34, scale_start_0).to_string(),
-		closest_edo_note(cents, 72, scale_start_0).to_string(),
-		closest_edo_note(cents, 96, scale_start_0).to_string(),
+//min-max in cents, e.g. 0-1200
+fn parse_cents_range(range: &str) -> Option<(f64, f64)> {
+	range.split_once('-')
+		.and_then(|(min, max)| Some((min.parse::<f64>().ok()?, max.parse::<f64>().ok()?)))
+		.filter(|(min, max)| min.is_finite() && max.is_finite() && min <= max)
+}
+
+//options without values, -1 would otherwise be taken for a negative number
+const FLAGS: [&str; 8] = ["-h", "--help", "-1", "--scale-start-1", "--no-flip", "--table", "--relative", "--no-approximations"];
+
+//options followed by values, with the number of values
+const VALUE_OPTIONS: [(&str, usize); 24] = [
+	("--scale", 1),
+	("--max-den", 1),
+	("--prime-limit", 1),
+	("--format", 1),
+	("--only", 1),
+	("--hide", 1),
+	("--edos", 1),
+	("--best-edo", 1),
+	("--edo-range", 1),
+	("--top", 1),
+	("--tolerance", 1),
+	("--base", 1),
+	("--bend-range", 1),
+	("--render", 1),
+	("--render-scale", 1),
+	("--timbre", 1),
+	("--arrangement", 1),
+	("--dissonance-curve", 1),
+	("--step", 1),
+	("--entropy-set", 1),
+	("--entropy-limit", 1),
+	("--entropy-spread", 1),
+	("--entropy-curve", 1),
+	("--export", 2),
+];
+
+//blocks of lines, one for each message and section, to be separated by blank lines
+fn report_text(report: &Report) -> Vec<String> {
+	let mut blocks = Vec::new();
+	
+	if report.common_factor > 1 {
+		blocks.push(format!("Interval reduced by common factor: {}", report.common_factor));
+	}
+	
+	if report.flipped {
+		match &report.pitch {
+			Pitch::Ratio(i) => blocks.push(format!("Interval flipped! Original: {}", i.inverse())),
+			Pitch::Cents(c) => blocks.push(format!("Interval flipped! Original: {}c", -c)),
+		}
+	}
+	
+	if report.has(ReportSection::Cents) {
+		let p = &report.pitch;
+		blocks.push(format!(
+			"\
+Interval                   : {}
+Decimal                    : {}
+Octaves                    : {}
+Tritaves                   : {}
+Cents                      : {}",
+			report.interval(),
+			p.to_f64(),
+			p.octaves(),
+			p.tritaves(),
+			p.cents(),
+		));
+	}
+	
+	if let (Some(f), true) = (&report.frequency, report.has(ReportSection::Frequency)) {
+		let bend = match f.pitch_bend {
+			Some(b) => b.to_string(),
+			None => "out of range".to_string(),
+		};
+		let (note, offset) = match &f.midi {
+			Some(m) => {
+				let plus = if m.offset > 0.0 { "+" } else { "" };
+				(format!("{} (MIDI {})", m.name(), m.note), format!("{}{}", plus, m.offset))
+			}
+			None => ("out of range".to_string(), "out of range".to_string()),
+		};
+		blocks.push(format!(
+			"\
+Base Frequency             : {}
+Frequency                  : {}
+Nearest 12-TET Note        : {}
+Cents off Note             : {}
+Pitch Bend                 : {} (range {} semitones)",
+			f.base,
+			f.frequency,
+			note,
+			offset,
+			bend,
+			f.bend_range,
+		));
+	}
+	
+	if let (Some(b), true) = (&report.beats, report.has(ReportSection::Beats)) {
+		let period = match (b.period, b.periodicity()) {
+			(Some(p), Some(f)) => format!("{} s ({})", p, f),
+			_ => "none, the interval is not a ratio".to_string(),
+		};
+		let mut lines = vec![
+			format!(
+				"Beats:\nUpper Note                 : {}\nPeriod                     : {}\nSethares Dissonance        : {}",
+				b.upper,
+				period,
+				b.dissonance
+			),
+			"\nBeat Rate of EDO Approximations:".to_string(),
+		];
+		for a in &b.approximations {
+			let beat = a.beat.map_or("-".to_string(), |beat| format!("{} Hz", beat));
+			lines.push(format!("{:<27}: {:<25}|  {}", a.scale, a.frequency.to_string(), beat));
+		}
+		lines.push(format!("\nNear Coincident Partials (within {} Hz):\nPartials                   : Hz                       |  beat rate", BEAT_LIMIT));
+		for p in &b.partials {
+			lines.push(format!(
+				"{:<27}: {:<25}|  {} Hz",
+				format!("{} of 1/1, {} of interval", p.lower, p.upper),
+				format!("{:.3} / {:.3}", p.lower_frequency, p.upper_frequency),
+				p.beat
+			));
+		}
+		blocks.push(lines.join("\n"));
+	}
+	
+	if let Some(r) = &report.ratio {
+		if report.has(ReportSection::Ratio) {
+			blocks.push(format!(
+				"\
+Numerator                  : {}
+Denominator                : {}",
+				r.interval.num,
+				r.interval.den,
+			));
+		}
 		
-		closest_bp_note(cents, scale_start_0).to_string(),
-		closest_tritave_19_note(cents, scale_start_0).to_string(),
+		//factors are always there when one of their sections is included
+		if let (Some(f), true) = (&r.factors, report.has(ReportSection::Limits)) {
+			let subgroup: Vec<String> = f.prime_subgroup.iter().map(|p| p.to_string()).collect();
+			let subgroup = if subgroup.is_empty() { "1".to_string() } else { subgroup.join(".") };
+			let bound = |complete: bool| if complete { "" } else { " (upper bound, not fully factored)" };
+			blocks.push(format!(
+				"\
+Tuning Limit               : {}{}
+Odd Limit                  : {}
+Integer Limit              : {}
+Prime Subgroup             : {}
+Numerator Tuning Limit     : {}{}
+Denominator Tuning Limit   : {}{}",
+				f.limit, bound(f.complete),
+				r.odd_limit,
+				r.integer_limit,
+				subgroup,
+				f.num_limit, bound(f.num_factors.is_complete()),
+				f.den_limit, bound(f.den_factors.is_complete()),
+			));
+		}
 		
-		closest_root_fifth_note(cents, 9, scale_start_0).to_string(),
-		closest_root_fifth_note(cents, 11, scale_start_0).to_string(),
-		closest_root_fifth_note(cents, 20, scale_start_0).to_string(),
+		if let (Some(f), true) = (&r.factors, report.has(ReportSection::Factors)) {
+			let monzo = match &f.monzo {
+				Some(m) => m.to_string(),
+				None if !f.complete => "not fully factored".to_string(),
+				None => format!("prime factor larger than {}", MONZO_PRIME_LIMIT)
+			};
+			blocks.push(format!(
+				"\
+Numerator Prime Factors    : {}
+Denominator Primes Factors : {}
+Monzo                      : {}",
+				f.num_factors,
+				f.den_factors,
+				monzo,
+			));
+		}
 		
-		closest_just_stack_note(cents, &Interval::new(16,15), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(10,9), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(9,8), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(8,7), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(7,6), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(6,5), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(5,4), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(4,3), scale_start_0).to_string(),
-		closest_just_stack_note(cents, &Interval::new(3,2), scale_start_0).to_string(),
+		if let (Some(f), true) = (&r.factors, report.has(ReportSection::Complexity)) {
+			blocks.push(format!(
+				"\
+Complexity:
+Tenney Height              : {}
+Benedetti Height           : {}
+Weil Height                : {}
+Wilson Complexity          : {}
+Gradus Suavitatis          : {}",
+				r.tenney_height,
+				r.benedetti_height.map_or("too large for u128".to_string(), |h| h.to_string()),
+				r.weil_height,
+				f.wilson_complexity.map_or("too large for u128".to_string(), |w| w.to_string()),
+				f.gradus_suavitatis.map_or("too large for u128".to_string(), |g| g.to_string()),
+			));
+		}
 		
-		closest_just_major_scale_note(cents, scale_start_0).to_string(),
-		closest_just_minor_scale_note(cents, scale_start_0).to_string(),
-		closest_just_chromatic_scale_note(cents, scale_start_0).to_string(),
-		closest_pythagorean_19_tone_tritave_scale_note(cents, scale_start_0).to_string(),
-		closest_just_harmonic_12_scale_note(cents, scale_start_0).to_string(),
-		closest_just_harmonic_16_scale_note(cents, scale_start_0).to_string(),
-		closest_just_bp_scale_note(cents, scale_start_0).to_string(),
+	}
+	
+	if let Some(entropy) = report.entropy {
+		blocks.push(format!("Harmonic Entropy           : {} nats", entropy));
+	}
+	
+	if let (Some(r), true) = (&report.ratio, report.has(ReportSection::Names)) {
+		blocks.push(format!("Name(s) : {}", r.name));
+	}
+	
+	if let Some(c) = &report.combination {
+		let row = |label: String, i: &Interval, edo_12: &Result<ScaleApproximation, ScaleError>| {
+			let note = match edo_12 {
+				Ok(a) => a.to_string(),
+				Err(e) => e.to_string(),
+			};
+			format!("{:<27}: {:<12}|  {:<32}|  {}", label, i.to_string(), note, i.get_name())
+		};
+		let mut lines = vec!["Combination Tones, relative to the lower note:\nTone                       : ratio       |  12-EDO|  cents off              |  name".to_string()];
+		lines.push(row("virtual fundamental".to_string(), &c.virtual_fundamental, &c.fundamental_edo_12));
+		for t in &c.tones {
+			let order = if t.order == 1 { "first" } else { "second" };
+			lines.push(row(format!("{} ({} {})", t.formula(), order, t.kind.name()), &t.interval, &t.edo_12));
+		}
+		blocks.push(lines.join("\n"));
+	}
+	
+	if report.has(ReportSection::Just) {
+		let mut lines = vec!["Nearest Just Intervals:\nRatio                      : cents off                |  name".to_string()];
+		for a in &report.nearest_just {
+			let plus = if a.error > 0.0 { "+" } else { "" };
+			let ratio = format!("{}{}", a.interval, if a.convergent { "" } else { " (semi)" });
+			lines.push(format!("{:<27}: {:<25}|  {}", ratio, format!("{}{}", plus, a.error), a.interval.get_name()));
+		}
+		blocks.push(lines.join("\n"));
+	}
+	
+	for (n, section) in report.approximations.iter().enumerate() {
+		let mut lines = vec![format!("{}:", section.title)];
+		if n == 0 {
+			lines.push("Scale / EDO / TET          : note  |  cents off".to_string());
+		}
+		for (m, group) in section.groups.iter().enumerate() {
+			if m > 0 {
+				lines.push(String::new());
+			}
+			for row in group {
+				lines.push(format!("{:<27}: {}", row.scale, show(&row.approximation)));
+			}
+		}
+		blocks.push(lines.join("\n"));
+	}
+	
+	blocks
+}
+
+fn show(approximation: &Result<ScaleApproximation, ScaleError>) -> String {
+	match approximation {
+		Ok(a) => a.to_string(),
+		Err(e) => e.to_string()
+	}
+}
+
+fn print_best_edos(intervals: &[(&str, f64)], min_div: u64, max_div: u64, top: usize, relative: bool) {
+	let names: Vec<&str> = intervals.iter().map(|(s, _)| *s).collect();
+	let cents: Vec<f64> = intervals.iter().map(|(_, c)| *c).collect();
+	
+	println!(
+		"Best EDOs from {} to {} for {}, by {} error:",
+		min_div,
+		max_div,
+		names.join(", "),
+		if relative { "relative (steps)" } else { "absolute (cents)" }
 	);
+	println!("EDO   : error                  |  note | cents off for each interval");
+	let rankings = match rank_edos(&cents, min_div, max_div, relative) {
+		Ok(r) => r,
+		Err(e) => {
+			println!("{}", e);
+			return;
+		}
+	};
+	for r in rankings.iter().take(top) {
+		let notes: Vec<String> = r.approximations.iter().map(|a| a.to_string()).collect();
+		println!("{:<6}: {:<23}|  {}", r.div, r.error, notes.join("  ||  "));
+	}
+}
+
+//value following the option, e.g. 100 for --max-den 100
+fn option_value<'a>(args: &'a [String], option: &str) -> Option<&'a String> {
+	let n = args.iter().position(|a| a == option)?;
+	args.get(n + 1)
+}
+
+fn builtin_scale(name: &str) -> Option<ScalaFile> {
+	let (description, scale) = match name {
+		"just-major" => ("Just major scale".to_string(), Scale::just_major()),
+		"just-minor" => ("Just minor scale".to_string(), Scale::just_minor()),
+		"just-chromatic" => ("Just chromatic scale".to_string(), Scale::just_chromatic()),
+		"pythagorean-19" => ("Pythagorean 19 tone tritave scale".to_string(), Scale::pythagorean_19_tone_tritave()),
+		"harmonic-12" => ("Harmonic 12 tone scale".to_string(), Scale::just_harmonic_12()),
+		"harmonic-16" => ("Harmonic 16 tone scale".to_string(), Scale::just_harmonic_16()),
+		"just-bp" => ("Just Bohlen Pierce scale".to_string(), Scale::just_bp()),
+		"bp" => ("Bohlen Pierce (TET version)".to_string(), Scale::bp()),
+		"tritave-19" => ("3/1 div 19".to_string(), Scale::tritave_19()),
+		_ => {
+			let (kind, arg) = name.split_once(':')?;
+			match kind {
+				"edo" => {
+					let div = arg.parse::<u64>().ok().filter(|d| *d > 0)?;
+					(format!("{}-EDO", div), Scale::edo(div))
+				}
+				"root-fifth" => {
+					let root = arg.parse::<u64>().ok().filter(|r| *r > 0)?;
+					(format!("(3/2)^(1/{})", root), Scale::root_fifth(root))
+				}
+				"stack" => {
+					//the interval is also the period, so it has to be larger than 1/1
+					let i = arg.parse::<Interval>().ok().filter(|i| *i > Interval::new(1,1))?;
+					(format!("{}/{} stacked", i.num, i.den), Scale::just_stack(&i))
+				}
+				_ => return None
+			}
+		}
+	};
+	Some(ScalaFile{description, scale})
 }
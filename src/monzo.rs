@@ -0,0 +1,170 @@
+//prime exponent vectors, e.g. 3/2 is [-1 1⟩
+//exponents are for consecutive primes starting at 2
+
+use std::{fmt, ops, str::FromStr};
+use crate::{is_prime, Interval, PrimeFactors};
+
+//monzos are dense, so intervals with prime factors past this are not converted
+pub const MONZO_PRIME_LIMIT: u128 = 65536;
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Monzo {
+	pub exponents: Vec<i32>
+}
+
+impl Monzo {
+	pub fn new(mut exponents: Vec<i32>) -> Self {
+		while exponents.last() == Some(&0) {
+			exponents.pop();
+		}
+		Self{exponents}
+	}
+	
+	//None if a prime factor is larger than MONZO_PRIME_LIMIT, or could not be found
+	pub fn from_interval(i: &Interval) -> Option<Self> {
+		let mut exponents = Vec::new();
+		for (factors, sign) in [(PrimeFactors::init(i.num), 1), (PrimeFactors::init(i.den), -1)] {
+			for (p, pow) in factors.iter() {
+				if p > MONZO_PRIME_LIMIT || !is_prime(p) {
+					return None;
+				}
+				let index = prime_index(p);
+				if exponents.len() <= index {
+					exponents.resize(index + 1, 0);
+				}
+				exponents[index] += sign * pow as i32;
+			}
+		}
+		Some(Self::new(exponents))
+	}
+	
+	//None if the numerator or denominator overflows
+	pub fn to_interval(&self) -> Option<Interval> {
+		let mut num: u128 = 1;
+		let mut den: u128 = 1;
+		for (e, p) in self.exponents.iter().zip(primes()) {
+			let power = p.checked_pow(e.unsigned_abs())?;
+			if *e > 0 {
+				num = num.checked_mul(power)?;
+			} else {
+				den = den.checked_mul(power)?;
+			}
+		}
+		Some(Interval::new(num, den))
+	}
+	
+	pub fn cents(&self) -> f64 {
+		self.exponents.iter().zip(primes())
+			.map(|(e, p)| *e as f64 * (p as f64).log2() * 1200.0)
+			.sum()
+	}
+	
+	//largest prime with a nonzero exponent, 1 for the unison
+	pub fn limit(&self) -> u128 {
+		primes().take(self.exponents.len()).last().unwrap_or(1)
+	}
+}
+
+impl ops::Add for &Monzo {
+	type Output = Monzo;
+	
+	fn add(self, other: &Monzo) -> Monzo {
+		let len = self.exponents.len().max(other.exponents.len());
+		Monzo::new((0..len).map(|n| {
+			self.exponents.get(n).unwrap_or(&0) + other.exponents.get(n).unwrap_or(&0)
+		}).collect())
+	}
+}
+
+impl ops::Sub for &Monzo {
+	type Output = Monzo;
+	
+	fn sub(self, other: &Monzo) -> Monzo {
+		self + &-other
+	}
+}
+
+impl ops::Neg for &Monzo {
+	type Output = Monzo;
+	
+	fn neg(self) -> Monzo {
+		Monzo::new(self.exponents.iter().map(|e| -e).collect())
+	}
+}
+
+impl ops::Add for Monzo {
+	type Output = Monzo;
+	
+	fn add(self, other: Monzo) -> Monzo {
+		&self + &other
+	}
+}
+
+impl ops::Sub for Monzo {
+	type Output = Monzo;
+	
+	fn sub(self, other: Monzo) -> Monzo {
+		&self - &other
+	}
+}
+
+impl ops::Neg for Monzo {
+	type Output = Monzo;
+	
+	fn neg(self) -> Monzo {
+		-&self
+	}
+}
+
+impl fmt::Display for Monzo {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let exponents: Vec<String> = self.exponents.iter().map(|e| e.to_string()).collect();
+		write!(f, "[{}⟩", exponents.join(" "))
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MonzoParseError {
+	MissingBrackets,
+	InvalidExponent(String)
+}
+
+impl fmt::Display for MonzoParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			MonzoParseError::MissingBrackets => write!(f, "monzo should be written like [-1 1⟩"),
+			MonzoParseError::InvalidExponent(s) => write!(f, "invalid monzo exponent: {}", s),
+		}
+	}
+}
+
+impl std::error::Error for MonzoParseError {}
+
+//accepts [a b c⟩, [a b c>, |a b c> and comma separated exponents
+impl FromStr for Monzo {
+	type Err = MonzoParseError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let inner = s.trim()
+			.strip_prefix(['[', '|'])
+			.and_then(|s| s.strip_suffix(['⟩', '>', ']']))
+			.ok_or(MonzoParseError::MissingBrackets)?;
+		
+		let exponents = inner.split(|c: char| c.is_whitespace() || c == ',')
+			.filter(|e| !e.is_empty())
+			.map(|e| e.parse::<i32>().map_err(|_| MonzoParseError::InvalidExponent(e.to_string())))
+			.collect::<Result<Vec<i32>, _>>()?;
+		
+		Ok(Self::new(exponents))
+	}
+}
+
+//2, 3, 5, 7, 11...
+pub fn primes() -> impl Iterator<Item = u128> {
+	(2..).filter(|n| is_prime(*n))
+}
+
+//position of the prime in the monzo, 2 is 0
+fn prime_index(p: u128) -> usize {
+	primes().take_while(|q| *q < p).count()
+}
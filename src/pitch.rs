@@ -0,0 +1,149 @@
+//pitches written in notations other than a plain ratio
+//some of them are irrational, so they can only be described in cents
+
+use std::str::FromStr;
+use crate::{parse_u128, Interval, IntervalParseError, Monzo};
+
+//pitches in cents are kept within the range of u128 ratios, 128 octaves either way from 1/1
+pub const MAX_CENTS: f64 = 153600.0;
+
+pub enum Pitch {
+	Ratio(Interval),
+	Cents(f64)
+}
+
+impl Pitch {
+	pub fn cents(&self) -> f64 {
+		match self {
+			Pitch::Ratio(i) => i.cents(),
+			Pitch::Cents(c) => *c,
+		}
+	}
+	
+	pub fn octaves(&self) -> f64 {
+		self.cents() / 1200.0
+	}
+	
+	pub fn tritaves(&self) -> f64 {
+		match self {
+			Pitch::Ratio(i) => i.tritaves(),
+			Pitch::Cents(_) => self.octaves() * 0.6309297535714575,
+		}
+	}
+	
+	pub fn to_f64(&self) -> f64 {
+		match self {
+			Pitch::Ratio(i) => i.to_f64(),
+			Pitch::Cents(_) => self.octaves().exp2(),
+		}
+	}
+	
+}
+
+impl FromStr for Pitch {
+	type Err = IntervalParseError;
+	
+	//accepts
+	//3/2 or 3      ratio
+	//1.5           decimal, exact
+	//701.955c      cents
+	//[-1 1⟩        monzo
+	//7\12          steps of an EDO, 7\13<3> for an equal division of another period
+	//3^(1/9)       power of a pitch, exact if the exponent is a whole number
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if s.is_empty() {
+			return Err(IntervalParseError::Empty);
+		}
+		
+		if s.starts_with(['[', '|']) {
+			let monzo = s.parse::<Monzo>()?;
+			return match monzo.to_interval() {
+				Some(i) => Ok(Pitch::Ratio(i)),
+				None => finite(monzo.cents()),
+			};
+		}
+		
+		if let Some(cents) = s.strip_suffix('c') {
+			let cents = parse_f64(cents).map_err(|_| IntervalParseError::NonNumeric(s.to_string()))?;
+			return finite(cents);
+		}
+		
+		if let Some((steps, div)) = s.split_once('\\') {
+			let (div, period) = match div.split_once('<') {
+				Some((div, period)) => {
+					let period = period.strip_suffix('>').ok_or(IntervalParseError::NonNumeric(period.to_string()))?;
+					(div, period.parse::<Pitch>()?.cents())
+				}
+				None => (div, 1200.0),
+			};
+			let steps = steps.trim().parse::<i64>().map_err(|_| IntervalParseError::NonNumeric(steps.to_string()))?;
+			let div = parse_u128(div.trim())?;
+			if div == 0 {
+				return Err(IntervalParseError::Zero);
+			}
+			return finite(period * steps as f64 / div as f64);
+		}
+		
+		if let Some((base, exp)) = s.split_once('^') {
+			let base = strip_parens(base).parse::<Pitch>()?;
+			let exp = strip_parens(exp);
+			
+			if let (Pitch::Ratio(i), Ok(exp)) = (&base, exp.parse::<i32>()) {
+				let (num, den) = if exp < 0 { (i.den, i.num) } else { (i.num, i.den) };
+				if let (Some(num), Some(den)) = (num.checked_pow(exp.unsigned_abs()), den.checked_pow(exp.unsigned_abs())) {
+					return Ok(Pitch::Ratio(Interval::new(num, den)));
+				}
+			}
+			
+			let exp = match exp.split_once('/') {
+				Some((p, q)) => parse_f64(p)? / parse_f64(q)?,
+				None => parse_f64(exp)?,
+			};
+			return finite(base.cents() * exp);
+		}
+		
+		if s.contains('.') {
+			return parse_decimal(s).map(Pitch::Ratio);
+		}
+		
+		if !s.contains('/') {
+			return format!("{}/1", s).parse::<Interval>().map(Pitch::Ratio);
+		}
+		
+		s.parse::<Interval>().map(Pitch::Ratio)
+	}
+}
+
+fn strip_parens(s: &str) -> &str {
+	let s = s.trim();
+	s.strip_prefix('(').and_then(|s| s.strip_suffix(')')).unwrap_or(s)
+}
+
+fn parse_f64(s: &str) -> Result<f64, IntervalParseError> {
+	s.trim().parse::<f64>().map_err(|_| IntervalParseError::NonNumeric(s.to_string()))
+}
+
+fn finite(cents: f64) -> Result<Pitch, IntervalParseError> {
+	if !cents.is_finite() {
+		Err(IntervalParseError::NotFinite)
+	} else if cents.abs() > MAX_CENTS {
+		Err(IntervalParseError::OutOfRange)
+	} else {
+		Ok(Pitch::Cents(cents))
+	}
+}
+
+//1.25 is 125/100, left unreduced like any other ratio input
+fn parse_decimal(s: &str) -> Result<Interval, IntervalParseError> {
+	let (whole, fraction) = s.split_once('.').ok_or(IntervalParseError::NonNumeric(s.to_string()))?;
+	if !(whole.chars().all(|c| c.is_ascii_digit()) && fraction.chars().all(|c| c.is_ascii_digit())) {
+		return Err(IntervalParseError::NonNumeric(s.to_string()));
+	}
+	let num = parse_u128(&format!("{}{}", whole, fraction))?;
+	let den = 10u128.checked_pow(fraction.len() as u32).ok_or(IntervalParseError::Overflow)?;
+	if num == 0 {
+		return Err(IntervalParseError::Zero);
+	}
+	Ok(Interval::new(num, den))
+}
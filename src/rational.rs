@@ -0,0 +1,78 @@
+//rational approximation of a decimal or cents value using continued fractions
+
+use crate::{largest_prime_factor, Interval};
+
+pub struct RationalApproximation {
+	pub interval: Interval,
+	//cents the interval is off from the approximated value
+	pub error: f64,
+	//false for semiconvergents
+	pub convergent: bool
+}
+
+//most semiconvergents listed before each convergent, the ones closest to it
+const MAX_SEMICONVERGENTS: u128 = 16;
+
+pub fn cents_to_ratio(cents: f64) -> f64 {
+	(cents / 1200.0).exp2()
+}
+
+//convergents and semiconvergents of the continued fraction of ratio, in order of increasing denominator
+//intervals outside of the prime limit are left out, but still used to find the next ones
+pub fn rational_approximations(ratio: f64, max_den: u128, prime_limit: Option<u128>) -> Vec<RationalApproximation> {
+	let mut approximations = Vec::new();
+	if !ratio.is_finite() || ratio <= 0.0 {
+		return approximations;
+	}
+	let cents = ratio.log2() * 1200.0;
+	
+	//previous two convergents, h/k
+	let (mut h1, mut k1): (u128, u128) = (1, 0);
+	let (mut h2, mut k2): (u128, u128) = (0, 1);
+	let mut x = ratio;
+	
+	//f64 runs out of precision long before this
+	for _ in 0..64 {
+		let a = x.floor();
+		if a > u64::MAX as f64 {
+			break;
+		}
+		let a = a as u128;
+		
+		//semiconvergents below half of a are never better than the previous convergent
+		//the first term only has integers below it, which are not worth listing
+		let first = match k1 {
+			0 => a,
+			_ => a.div_ceil(2).max(a.saturating_sub(MAX_SEMICONVERGENTS)).max(1),
+		};
+		for m in first..=a {
+			let (Some(h), Some(k)) = (m.checked_mul(h1).and_then(|h| h.checked_add(h2)), m.checked_mul(k1).and_then(|k| k.checked_add(k2))) else {
+				return approximations;
+			};
+			if k > max_den {
+				return approximations;
+			}
+			if h == 0 {
+				continue;
+			}
+			//always in lowest terms, neighbouring convergents have a determinant of 1
+			let interval = Interval::new(h, k);
+			if prime_limit.is_some_and(|limit| largest_prime_factor(h).max(largest_prime_factor(k)) > limit) {
+				continue;
+			}
+			let error = interval.cents() - cents;
+			approximations.push(RationalApproximation{interval, error, convergent: m == a});
+		}
+		
+		(h1, h2) = (a * h1 + h2, h1);
+		(k1, k2) = (a * k1 + k2, k1);
+		
+		let fraction = x - x.floor();
+		//exact, or as close as f64 can tell
+		if fraction < 1e-12 || (h1 as f64 / k1 as f64 - ratio).abs() <= f64::EPSILON * ratio {
+			break;
+		}
+		x = 1.0 / fraction;
+	}
+	approximations
+}
@@ -0,0 +1,634 @@
+//everything shown about an interval, gathered in one place so it can be printed as text or JSON
+
+use std::{error::Error, fmt};
+use crate::*;
+
+//parts of the report that can be shown or left out on their own
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportSection {
+	//interval, decimal, octaves, tritaves and cents
+	Cents,
+	//frequency and MIDI note above the base frequency, if there is one
+	Frequency,
+	//periodicity and beat rates at the base frequency
+	Beats,
+	//numerator and denominator
+	Ratio,
+	Limits,
+	//prime factors and monzo
+	Factors,
+	Complexity,
+	//harmonic entropy
+	Entropy,
+	Names,
+	//difference and summation tones and the virtual fundamental, only for ratios
+	Combination,
+	//nearest just intervals
+	Just,
+	Edo,
+	NonOctave,
+	NonTet
+}
+
+impl ReportSection {
+	pub const ALL: [ReportSection; 14] = [
+		ReportSection::Cents,
+		ReportSection::Frequency,
+		ReportSection::Beats,
+		ReportSection::Ratio,
+		ReportSection::Limits,
+		ReportSection::Factors,
+		ReportSection::Complexity,
+		ReportSection::Entropy,
+		ReportSection::Names,
+		ReportSection::Combination,
+		ReportSection::Just,
+		ReportSection::Edo,
+		ReportSection::NonOctave,
+		ReportSection::NonTet,
+	];
+	
+	pub const APPROXIMATIONS: [ReportSection; 3] = [ReportSection::Edo, ReportSection::NonOctave, ReportSection::NonTet];
+	
+	pub fn name(&self) -> &'static str {
+		match self {
+			ReportSection::Cents => "cents",
+			ReportSection::Frequency => "frequency",
+			ReportSection::Beats => "beats",
+			ReportSection::Ratio => "ratio",
+			ReportSection::Limits => "limits",
+			ReportSection::Factors => "factors",
+			ReportSection::Complexity => "complexity",
+			ReportSection::Entropy => "entropy",
+			ReportSection::Names => "names",
+			ReportSection::Combination => "combination",
+			ReportSection::Just => "just",
+			ReportSection::Edo => "edo",
+			ReportSection::NonOctave => "non-octave",
+			ReportSection::NonTet => "non-tet",
+		}
+	}
+	
+	//comma separated names, approximations is short for edo,non-octave,non-tet
+	pub fn parse_list(list: &str) -> Result<Vec<ReportSection>, UnknownSection> {
+		let mut sections = Vec::new();
+		for name in list.split(',').map(|n| n.trim()).filter(|n| !n.is_empty()) {
+			if name == "approximations" {
+				sections.extend(Self::APPROXIMATIONS);
+				continue;
+			}
+			match Self::ALL.iter().find(|s| s.name() == name) {
+				Some(s) => sections.push(*s),
+				None => return Err(UnknownSection(name.to_string())),
+			}
+		}
+		Ok(sections)
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownSection(pub String);
+
+impl fmt::Display for UnknownSection {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let names: Vec<&str> = ReportSection::ALL.iter().map(|s| s.name()).collect();
+		write!(f, "unknown section {}, sections are {}, approximations", self.0, names.join(", "))
+	}
+}
+
+impl Error for UnknownSection {}
+
+pub struct ReportOptions {
+	pub auto_flip: bool,
+	pub scale_start_0: bool,
+	//limits for the nearest just intervals
+	pub max_den: u128,
+	pub prime_limit: Option<u128>,
+	//extra scales to approximate with, e.g. loaded from Scala files, with their labels
+	pub scales: Vec<(String, Scale)>,
+	//sections to include, in any order
+	pub sections: Vec<ReportSection>,
+	//EDOs to approximate with instead of the built-in ones
+	pub edos: Option<Vec<u64>>,
+	//reference pitch of 1/1, for the frequency section
+	pub base: Option<Frequency>,
+	//pitch bend range in semitones
+	pub bend_range: f64,
+	//spectrum of both notes, for the dissonance
+	pub timbre: Timbre,
+	//ratios, limit and spread in cents of the harmonic entropy
+	pub entropy_set: RatioSet,
+	pub entropy_limit: u128,
+	pub entropy_spread: f64
+}
+
+impl Default for ReportOptions {
+	fn default() -> Self {
+		Self{
+			auto_flip: true,
+			scale_start_0: true,
+			max_den: 1000,
+			prime_limit: None,
+			scales: Vec::new(),
+			sections: ReportSection::ALL.to_vec(),
+			edos: None,
+			base: None,
+			bend_range: 2.0,
+			timbre: Timbre::Harmonic,
+			entropy_set: RatioSet::Tenney,
+			entropy_limit: RatioSet::Tenney.default_limit(),
+			entropy_spread: 17.0
+		}
+	}
+}
+
+pub struct Report {
+	pub input: String,
+	//1 if the ratio was already in lowest terms
+	pub common_factor: u128,
+	//true if the input was below 1/1 and was inverted
+	pub flipped: bool,
+	//reduced and flipped
+	pub pitch: Pitch,
+	//only for pitches that are a ratio
+	pub ratio: Option<RatioReport>,
+	//only if there is a base frequency
+	pub frequency: Option<FrequencyReport>,
+	pub beats: Option<BeatReport>,
+	//only if the entropy section is included
+	pub entropy: Option<f64>,
+	//only for ratios, if the combination section is included
+	pub combination: Option<CombinationReport>,
+	//sections left out are empty
+	pub nearest_just: Vec<RationalApproximation>,
+	pub approximations: Vec<ApproximationSection>,
+	//included sections, in report order
+	pub sections: Vec<ReportSection>
+}
+
+pub struct RatioReport {
+	pub interval: Interval,
+	pub odd_limit: u128,
+	pub integer_limit: u128,
+	pub tenney_height: f64,
+	pub benedetti_height: Option<u128>,
+	pub weil_height: u128,
+	pub name: String,
+	//only if the limits, factors or complexity sections are included, factoring large ratios can be slow
+	pub factors: Option<RatioFactors>
+}
+
+//everything that needs the prime factors of the ratio
+pub struct RatioFactors {
+	pub limit: u128,
+	pub prime_subgroup: Vec<u128>,
+	pub num_limit: u128,
+	pub den_limit: u128,
+	pub num_factors: PrimeFactors,
+	pub den_factors: PrimeFactors,
+	//false if a composite factor could not be split, the limits are then only upper bounds
+	pub complete: bool,
+	//None if a prime factor is larger than MONZO_PRIME_LIMIT
+	pub monzo: Option<Monzo>,
+	//None if they overflow
+	pub wilson_complexity: Option<u128>,
+	pub gradus_suavitatis: Option<u128>
+}
+
+pub struct ApproximationRow {
+	pub scale: String,
+	pub approximation: Result<ScaleApproximation, ScaleError>
+}
+
+//rows of related scales are grouped together inside a section
+pub struct ApproximationSection {
+	pub section: ReportSection,
+	pub title: &'static str,
+	pub groups: Vec<Vec<ApproximationRow>>
+}
+
+impl Report {
+	pub fn new(input: &str, pitch: Pitch, options: &ReportOptions) -> Self {
+		let mut common_factor = 1;
+		let mut flipped = false;
+		
+		let pitch = match pitch {
+			Pitch::Ratio(mut i) => {
+				common_factor = i.reduce();
+				if options.auto_flip && i.den > i.num {
+					flipped = true;
+					i = i.inverse();
+				}
+				Pitch::Ratio(i)
+			}
+			Pitch::Cents(cents) => {
+				if options.auto_flip && cents < 0.0 {
+					flipped = true;
+					Pitch::Cents(-cents)
+				} else {
+					Pitch::Cents(cents)
+				}
+			}
+		};
+		
+		let sections: Vec<ReportSection> = ReportSection::ALL.into_iter().filter(|s| options.sections.contains(s)).collect();
+		
+		let factored = [ReportSection::Limits, ReportSection::Factors, ReportSection::Complexity].iter().any(|s| sections.contains(s));
+		let ratio = match pitch {
+			Pitch::Ratio(i) => Some(RatioReport::new(i, factored)),
+			Pitch::Cents(_) => None,
+		};
+		let cents = pitch.cents();
+		
+		let nearest_just = if sections.contains(&ReportSection::Just) {
+			rational_approximations(cents_to_ratio(cents), options.max_den, options.prime_limit)
+		} else {
+			Vec::new()
+		};
+		
+		let approximations = approximation_sections(cents, options);
+		
+		let frequency = options.base.map(|base| FrequencyReport::new(base, cents, options.bend_range));
+		let beats = options.base.map(|base| {
+			let edos: Vec<&ApproximationSection> = approximations.iter().filter(|a| a.section == ReportSection::Edo).collect();
+			let rows: Vec<&ApproximationRow> = edos.iter().flat_map(|a| a.groups.iter().flatten()).collect();
+			BeatReport::new(base, &pitch, &rows, &options.timbre.partials())
+		});
+		
+		let approximations = approximations.into_iter()
+			.filter(|a| sections.contains(&a.section))
+			.collect();
+		
+		let entropy = if sections.contains(&ReportSection::Entropy) {
+			Some(HarmonicEntropy::new(options.entropy_set, options.entropy_limit, options.entropy_spread).entropy(cents))
+		} else {
+			None
+		};
+		
+		let combination = match pitch {
+			Pitch::Ratio(i) if sections.contains(&ReportSection::Combination) => Some(CombinationReport::new(i, options.scale_start_0)),
+			_ => None,
+		};
+		
+		Self{
+			input: input.to_string(),
+			common_factor,
+			flipped,
+			pitch,
+			ratio,
+			frequency,
+			beats,
+			entropy,
+			combination,
+			nearest_just,
+			approximations,
+			sections
+		}
+	}
+	
+	pub fn has(&self, section: ReportSection) -> bool {
+		self.sections.contains(&section)
+	}
+	
+	//the ratio, or the input as written unless it was flipped, then the flipped cents
+	pub fn interval(&self) -> String {
+		match &self.pitch {
+			Pitch::Ratio(i) => i.to_string(),
+			Pitch::Cents(c) if self.flipped => format!("{}c", c),
+			Pitch::Cents(_) => self.input.clone(),
+		}
+	}
+}
+
+impl RatioReport {
+	pub fn new(i: Interval, factored: bool) -> Self {
+		Self{
+			interval: i,
+			odd_limit: i.odd_limit(),
+			integer_limit: i.integer_limit(),
+			tenney_height: i.tenney_height(),
+			benedetti_height: i.benedetti_height(),
+			weil_height: i.weil_height(),
+			name: i.get_name().to_string(),
+			factors: factored.then(|| RatioFactors::new(i))
+		}
+	}
+}
+
+impl RatioFactors {
+	//factors each side once, the Interval methods would factor them again for every value
+	pub fn new(i: Interval) -> Self {
+		let num_factors = PrimeFactors::init(i.num);
+		let den_factors = PrimeFactors::init(i.den);
+		let largest = |f: &PrimeFactors| f.iter().last().map_or(1, |(p, _)| p);
+		let num_limit = largest(&num_factors);
+		let den_limit = largest(&den_factors);
+		let both: Vec<(u128, u8)> = num_factors.iter().chain(den_factors.iter()).collect();
+		let metrics = FactorMetrics::new(&both);
+		Self{
+			limit: num_limit.max(den_limit),
+			prime_subgroup: metrics.prime_subgroup,
+			num_limit,
+			den_limit,
+			complete: num_factors.is_complete() && den_factors.is_complete(),
+			monzo: Monzo::from_interval(&i),
+			wilson_complexity: metrics.wilson_complexity,
+			gradus_suavitatis: metrics.gradus_suavitatis,
+			num_factors,
+			den_factors
+		}
+	}
+}
+
+//labels of the built-in EDOs, grouped as they are shown
+const EDO_GROUPS: [&[(u64, &str)]; 4] = [
+	&[
+		(12, "12 standard semi tone scale"),
+		(24, "24 quarter tone scale"),
+		(19, "19 ~ 1/3 comma meantone ext"),
+		(31, "31 ~ 1/4 comma meantone ext"),
+		(41, "41"),
+		(53, "53"),
+	],
+	&[
+		(1, "1 octave scale"),
+		(2, "2 12tet tritone scale"),
+		(3, "3 12tet major third scale"),
+		(4, "4 12tet minor third scale"),
+	],
+	&[
+		(5, "5"),
+		(6, "6 whole tone scale"),
+		(7, "7"),
+		(8, "8 neutral second, 3/2 tone"),
+		(9, "9 2/3 tone scale"),
+		(10, "10 5*2"),
+	],
+	&[
+		(15, "15 5*3"),
+		(17, "17"),
+		(22, "22"),
+		(23, "23"),
+		(26, "26"),
+		(27, "27 9*3"),
+		(29, "29"),
+		(34, "34 17*2"),
+		(72, "72 sixth tone scale"),
+		(96, "96 eighth tone scale"),
+	],
+];
+
+//the built-in scales, or the chosen EDOs instead of the built-in ones, with the extra scales as the last group of the EDOs
+pub fn approximation_sections(cents: f64, options: &ReportOptions) -> Vec<ApproximationSection> {
+	let scale_start_0 = options.scale_start_0;
+	let row = |scale: &str, approximation| ApproximationRow{scale: scale.to_string(), approximation};
+	let edo = |scale: &str, div| row(scale, closest_edo_note(cents, div, scale_start_0));
+	let stack = |scale: &str, num, den| row(scale, closest_just_stack_note(cents, &Interval::new(num, den), scale_start_0));
+	
+	let mut edo_groups = match &options.edos {
+		Some(divs) => vec![divs.iter().map(|div| {
+			let label = EDO_GROUPS.iter().flat_map(|g| g.iter()).find(|(d, _)| d == div);
+			match label {
+				Some((_, label)) => edo(label, *div),
+				None => edo(&div.to_string(), *div),
+			}
+		}).collect()],
+		None => EDO_GROUPS.iter().map(|g| g.iter().map(|(div, label)| edo(label, *div)).collect()).collect(),
+	};
+	if !options.scales.is_empty() {
+		edo_groups.push(options.scales.iter().map(|(label, scale)| row(label, scale.closest_note(cents, scale_start_0))).collect());
+	}
+	
+	let non_tet = vec![
+		row("Just major scale", closest_just_major_scale_note(cents, scale_start_0)),
+		row("Just minor scale", closest_just_minor_scale_note(cents, scale_start_0)),
+		row("Just chromatic scale", closest_just_chromatic_scale_note(cents, scale_start_0)),
+		row("Pythagorean scale", closest_pythagorean_19_tone_tritave_scale_note(cents, scale_start_0)),
+		row("Harmonic 12 tone scale", closest_just_harmonic_12_scale_note(cents, scale_start_0)),
+		row("Harmonic 16 tone scale", closest_just_harmonic_16_scale_note(cents, scale_start_0)),
+		row("Just Bohlen Pierce scale", closest_just_bp_scale_note(cents, scale_start_0)),
+	];
+	
+	vec![
+		ApproximationSection{section: ReportSection::Edo, title: "Equal Temperament Approximations", groups: edo_groups},
+		ApproximationSection{section: ReportSection::NonOctave, title: "Non Octave TET Scales", groups: vec![
+			vec![
+				row("Bohlen Pierce (TET version)", closest_bp_note(cents, scale_start_0)),
+				row("3/1 div 19", closest_tritave_19_note(cents, scale_start_0)),
+			],
+			vec![
+				row("Alpha (3/2)^(1/9)  ~78.0c", closest_root_fifth_note(cents, 9, scale_start_0)),
+				row("Beta  (3/2)^(1/11) ~63.8c", closest_root_fifth_note(cents, 11, scale_start_0)),
+				row("Gamma (3/2)^(1/20) ~35.1c", closest_root_fifth_note(cents, 20, scale_start_0)),
+			],
+			vec![
+				stack("16/15 just semitone scale", 16, 15),
+				stack("10/9 just minor whole tone", 10, 9),
+				stack("9/8 just major whole tone", 9, 8),
+				stack("8/7 septimal whole tone", 8, 7),
+				stack("7/6 septimal minor third", 7, 6),
+				stack("6/5 just minor third scale", 6, 5),
+				stack("5/4 just major third scale", 5, 4),
+				stack("4/3 just perfect fourth", 4, 3),
+				stack("3/2 just perfect fifth", 3, 2),
+			],
+		]},
+		ApproximationSection{section: ReportSection::NonTet, title: "Non TET Scales", groups: vec![non_tet]},
+	]
+}
+
+impl ToJson for Interval {
+	fn to_json(&self) -> Json {
+		Json::object()
+			.with("num", self.num)
+			.with("den", self.den)
+	}
+}
+
+impl ToJson for ScaleApproximation {
+	fn to_json(&self) -> Json {
+		Json::object()
+			.with("note", self.note)
+			.with("offset", self.offset)
+	}
+}
+
+impl ToJson for PrimeFactors {
+	fn to_json(&self) -> Json {
+		Json::Array(self.iter().map(|(prime, power)| {
+			Json::object()
+				.with("prime", prime)
+				.with("power", power as u64)
+		}).collect())
+	}
+}
+
+impl ToJson for Monzo {
+	fn to_json(&self) -> Json {
+		self.exponents.clone().into()
+	}
+}
+
+impl ToJson for RationalApproximation {
+	fn to_json(&self) -> Json {
+		Json::object()
+			.with("ratio", self.interval.to_string())
+			.with("error", self.error)
+			.with("convergent", self.convergent)
+			.with("names", names(&self.interval))
+	}
+}
+
+impl ApproximationRow {
+	fn to_json_in(&self, section: &str) -> Json {
+		let row = Json::object()
+			.with("section", section)
+			.with("scale", self.scale.as_str());
+		match &self.approximation {
+			Ok(a) => row.with("note", a.note).with("offset", a.offset),
+			Err(e) => row.with("error", e.to_string()),
+		}
+	}
+}
+
+//ratio relative to the lower note with its names and closest 12-EDO note, added to json
+fn combination_tone_json(json: Json, i: &Interval, edo_12: &Result<ScaleApproximation, ScaleError>) -> Json {
+	let json = json
+		.with("ratio", i.to_string())
+		.with("cents", i.cents())
+		.with("names", names(i));
+	match edo_12 {
+		Ok(a) => json.with("edo_12", a.to_json()),
+		Err(e) => json.with("edo_12", e.to_string()),
+	}
+}
+
+impl ToJson for CombinationReport {
+	fn to_json(&self) -> Json {
+		Json::object()
+			.with("virtual_fundamental", combination_tone_json(Json::object(), &self.virtual_fundamental, &self.fundamental_edo_12))
+			.with("tones", Json::Array(self.tones.iter().map(|t| {
+				let json = Json::object()
+					.with("formula", t.formula())
+					.with("kind", t.kind.name())
+					.with("order", t.order as u64);
+				combination_tone_json(json, &t.interval, &t.edo_12)
+			}).collect()))
+	}
+}
+
+impl ToJson for Report {
+	fn to_json(&self) -> Json {
+		let mut json = Json::object()
+			.with("input", self.input.as_str())
+			.with("interval", self.interval())
+			.with("common_factor", self.common_factor)
+			.with("flipped", self.flipped);
+		
+		if self.has(ReportSection::Cents) {
+			json = json
+				.with("decimal", self.pitch.to_f64())
+				.with("octaves", self.pitch.octaves())
+				.with("tritaves", self.pitch.tritaves())
+				.with("cents", self.pitch.cents());
+		}
+		
+		if let (Some(f), true) = (&self.frequency, self.has(ReportSection::Frequency)) {
+			json = json.with("frequency", Json::object()
+				.with("base", f.base.0)
+				.with("hz", f.frequency.0)
+				.with("note", f.midi.as_ref().map(|m| m.name()))
+				.with("midi_note", f.midi.as_ref().map(|m| m.note))
+				.with("offset", f.midi.as_ref().map(|m| m.offset))
+				.with("bend_range", f.bend_range)
+				.with("pitch_bend", f.pitch_bend.map(|b| b as u64)));
+		}
+		
+		if let (Some(b), true) = (&self.beats, self.has(ReportSection::Beats)) {
+			json = json.with("beats", Json::object()
+				.with("period", b.period)
+				.with("periodicity", b.periodicity().map(|f| f.0))
+				.with("dissonance", b.dissonance)
+				.with("approximations", Json::Array(b.approximations.iter().map(|a| {
+					Json::object()
+						.with("scale", a.scale.as_str())
+						.with("hz", a.frequency.0)
+						.with("beat", a.beat)
+				}).collect()))
+				.with("partials", Json::Array(b.partials.iter().map(|p| {
+					Json::object()
+						.with("lower_partial", p.lower as u64)
+						.with("upper_partial", p.upper as u64)
+						.with("lower_hz", p.lower_frequency)
+						.with("upper_hz", p.upper_frequency)
+						.with("beat", p.beat)
+				}).collect())));
+		}
+		
+		if let Some(r) = &self.ratio {
+			if self.has(ReportSection::Ratio) {
+				json = json
+					.with("numerator", r.interval.num)
+					.with("denominator", r.interval.den);
+			}
+			//factors are always there when one of their sections is included
+			if let (Some(f), true) = (&r.factors, self.has(ReportSection::Limits)) {
+				json = json.with("limits", Json::object()
+					.with("tuning", f.limit)
+					.with("odd", r.odd_limit)
+					.with("integer", r.integer_limit)
+					.with("prime_subgroup", f.prime_subgroup.clone())
+					.with("numerator", f.num_limit)
+					.with("denominator", f.den_limit)
+					.with("complete", f.complete));
+			}
+			if let (Some(f), true) = (&r.factors, self.has(ReportSection::Factors)) {
+				json = json
+					.with("factorization", Json::object()
+						.with("numerator", f.num_factors.to_json())
+						.with("denominator", f.den_factors.to_json())
+						.with("complete", f.complete))
+					.with("monzo", f.monzo.as_ref().map(|m| m.to_json()));
+			}
+			if let (Some(f), true) = (&r.factors, self.has(ReportSection::Complexity)) {
+				json = json.with("complexity", Json::object()
+					.with("tenney_height", r.tenney_height)
+					.with("benedetti_height", r.benedetti_height)
+					.with("weil_height", r.weil_height)
+					.with("wilson_complexity", f.wilson_complexity)
+					.with("gradus_suavitatis", f.gradus_suavitatis));
+			}
+			if self.has(ReportSection::Names) {
+				json = json.with("names", names(&r.interval));
+			}
+		}
+		
+		if let Some(entropy) = self.entropy {
+			json = json.with("harmonic_entropy", entropy);
+		}
+		
+		if let Some(c) = &self.combination {
+			json = json.with("combination_tones", c.to_json());
+		}
+		
+		if self.has(ReportSection::Just) {
+			json = json.with("nearest_just", Json::Array(self.nearest_just.iter().map(|a| a.to_json()).collect()));
+		}
+		
+		if !self.approximations.is_empty() {
+			json = json.with("approximations", Json::Array(self.approximations.iter().flat_map(|section| {
+				section.groups.iter().flatten().map(|row| row.to_json_in(section.title))
+			}).collect()));
+		}
+		
+		json
+	}
+}
+
+//every name the interval is known by, empty if it has none
+fn names(i: &Interval) -> Vec<&'static str> {
+	match NAMES.iter().find(|n| n.interval == *i) {
+		Some(n) => n.name.split(", ").collect(),
+		None => Vec::new()
+	}
+}
@@ -0,0 +1,225 @@
+//Scala scale file (.scl) support
+//format reference: https://www.huygens-fokker.org/scala/scl_format.html
+
+use std::{fmt, fs, io, path::Path};
+use crate::{Interval, Scale};
+
+pub struct ScalaFile {
+	pub description: String,
+	pub scale: Scale
+}
+
+#[derive(Debug)]
+pub enum ScalaError {
+	Io(io::Error),
+	MissingDescription,
+	MissingNoteCount,
+	InvalidNoteCount(String),
+	InvalidPitch(String),
+	WrongNoteCount{expected: usize, found: usize},
+	InvalidPeriod,
+	Empty
+}
+
+impl fmt::Display for ScalaError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ScalaError::Io(e) => write!(f, "could not read scale file: {}", e),
+			ScalaError::MissingDescription => write!(f, "missing description line"),
+			ScalaError::MissingNoteCount => write!(f, "missing note count line"),
+			ScalaError::InvalidNoteCount(s) => write!(f, "invalid note count: {}", s),
+			ScalaError::InvalidPitch(s) => write!(f, "invalid pitch: {}", s),
+			ScalaError::WrongNoteCount{expected, found} => write!(f, "expected {} notes, found {}", expected, found),
+			ScalaError::InvalidPeriod => write!(f, "the last pitch is the period, it has to be above 0 cents"),
+			ScalaError::Empty => write!(f, "scale has no notes"),
+		}
+	}
+}
+
+impl std::error::Error for ScalaError {}
+
+impl From<io::Error> for ScalaError {
+	fn from(e: io::Error) -> Self {
+		ScalaError::Io(e)
+	}
+}
+
+impl ScalaFile {
+	pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, ScalaError> {
+		Self::parse(&fs::read_to_string(path)?)
+	}
+	
+	//the unison is implied, the last pitch is the period
+	pub fn parse(s: &str) -> Result<Self, ScalaError> {
+		//comments start with '!', the description may be blank so only comments are skipped
+		let mut lines = s.lines().filter(|l| !l.starts_with('!'));
+		
+		let description = lines.next().ok_or(ScalaError::MissingDescription)?.trim().to_string();
+		
+		let count = lines.next().ok_or(ScalaError::MissingNoteCount)?.trim();
+		let count = count.split_whitespace().next().unwrap_or("")
+			.parse::<usize>().map_err(|_| ScalaError::InvalidNoteCount(count.to_string()))?;
+		
+		if count == 0 {
+			return Err(ScalaError::Empty);
+		}
+		
+		let mut pitches = Vec::with_capacity(count);
+		for l in lines {
+			//anything after the pitch value is ignored
+			let Some(value) = l.split_whitespace().next() else {
+				continue;
+			};
+			pitches.push(parse_pitch(value)?);
+		}
+		
+		if pitches.len() != count {
+			return Err(ScalaError::WrongNoteCount{expected: count, found: pitches.len()});
+		}
+		
+		let (period, period_ratio) = pitches[count - 1];
+		if period <= 0.0 {
+			return Err(ScalaError::InvalidPeriod);
+		}
+		
+		//the other pitches can be in any order and outside of the period, which is allowed by the format
+		//they are moved into the period and sorted so the closest note can be found by binary search
+		let mut steps: Vec<(f64, Option<Interval>)> = pitches[..count - 1].iter().map(|(cents, ratio)| {
+			let within = cents.rem_euclid(period);
+			let ratio = match (ratio, period_ratio) {
+				_ if within == *cents => *ratio,
+				(Some(r), Some(p)) => r.checked_normalize_to(p),
+				_ => None,
+			};
+			(within, ratio)
+		}).collect();
+		steps.sort_by(|a, b| a.0.total_cmp(&b.0));
+		
+		let mut scale = Scale::new(period, Vec::with_capacity(count));
+		scale.steps.push(0.0);
+		scale.ratios.push(Some(Interval::new(1,1)));
+		for (cents, ratio) in steps {
+			scale.steps.push(cents);
+			scale.ratios.push(ratio);
+		}
+		scale.ratios.push(period_ratio);
+		
+		Ok(Self{description, scale})
+	}
+	
+	pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		fs::write(path, self.to_string())
+	}
+}
+
+//writes the .scl file, ratios are used where they are known
+impl fmt::Display for ScalaFile {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let scale = &self.scale;
+		writeln!(f, "! exported by interval-info")?;
+		writeln!(f, "!")?;
+		writeln!(f, "{}", self.description)?;
+		writeln!(f, " {}", scale.len())?;
+		writeln!(f, "!")?;
+		
+		//the tonic is implied, and the period closes the scale
+		for step in 1..=scale.len() {
+			match scale.ratio(step) {
+				Some(r) => writeln!(f, " {}/{}", r.num, r.den)?,
+				None => {
+					let cents = if step == scale.len() { scale.period } else { scale.steps[step] };
+					writeln!(f, " {}", float_string(cents))?
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+//cents need a decimal point so they are not read as a ratio, the .kbm frequency is also written this way
+fn float_string(x: f64) -> String {
+	let s = x.to_string();
+	if s.contains('.') { s } else { s + ".0" }
+}
+
+//Scala keyboard mapping (.kbm)
+//format reference: https://www.huygens-fokker.org/scala/help.htm#mappings
+pub struct KeyboardMapping {
+	pub first_note: u8,
+	pub last_note: u8,
+	//midi note the tonic of the scale is mapped to
+	pub middle_note: u8,
+	pub reference_note: u8,
+	pub reference_freq: f64,
+	//scale degree of the period
+	pub period_degree: usize,
+	//scale degree of each key in a period, None for keys that are not mapped
+	pub mapping: Vec<Option<usize>>
+}
+
+impl KeyboardMapping {
+	//every key gets the next scale degree, with the tonic on middle C at its 12-EDO frequency
+	pub fn linear(scale: &Scale) -> Self {
+		Self{
+			first_note: 0,
+			last_note: 127,
+			middle_note: 60,
+			reference_note: 60,
+			reference_freq: 261.6255653005986,
+			period_degree: scale.len(),
+			mapping: (0..scale.len()).map(Some).collect()
+		}
+	}
+	
+	pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		fs::write(path, self.to_string())
+	}
+}
+
+impl fmt::Display for KeyboardMapping {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "! exported by interval-info")?;
+		writeln!(f, "! Map size:")?;
+		writeln!(f, "{}", self.mapping.len())?;
+		writeln!(f, "! First MIDI note number to retune:")?;
+		writeln!(f, "{}", self.first_note)?;
+		writeln!(f, "! Last MIDI note number to retune:")?;
+		writeln!(f, "{}", self.last_note)?;
+		writeln!(f, "! Middle note where the first entry of the mapping is mapped to:")?;
+		writeln!(f, "{}", self.middle_note)?;
+		writeln!(f, "! Reference note for which frequency is given:")?;
+		writeln!(f, "{}", self.reference_note)?;
+		writeln!(f, "! Frequency to tune the above note to:")?;
+		writeln!(f, "{}", float_string(self.reference_freq))?;
+		writeln!(f, "! Scale degree to consider as formal octave:")?;
+		writeln!(f, "{}", self.period_degree)?;
+		writeln!(f, "! Mapping.")?;
+		for degree in &self.mapping {
+			match degree {
+				Some(d) => writeln!(f, "{}", d)?,
+				None => writeln!(f, "x")?,
+			}
+		}
+		Ok(())
+	}
+}
+
+//pitches with a decimal point are in cents, otherwise they are a ratio or a whole number
+fn parse_pitch(s: &str) -> Result<(f64, Option<Interval>), ScalaError> {
+	let invalid = || ScalaError::InvalidPitch(s.to_string());
+	
+	if s.contains('.') {
+		let cents = s.parse::<f64>().ok().filter(|c| c.is_finite()).ok_or_else(invalid)?;
+		return Ok((cents, None));
+	}
+	
+	let (num, den) = s.split_once('/').unwrap_or((s, "1"));
+	let num = num.parse::<u128>().map_err(|_| invalid())?;
+	let den = den.parse::<u128>().map_err(|_| invalid())?;
+	if num == 0 || den == 0 {
+		return Err(invalid());
+	}
+	
+	let ratio = Interval::new(num, den);
+	Ok((ratio.cents(), Some(ratio)))
+}
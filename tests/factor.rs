@@ -0,0 +1,84 @@
+//checks the Miller-Rabin and Pollard rho code against slow but obviously correct versions
+
+use interval_info::*;
+
+fn trial_division_is_prime(n: u128) -> bool {
+	if n < 2 {
+		return false;
+	}
+	let mut d = 2;
+	while d * d <= n {
+		if n.is_multiple_of(d) {
+			return false;
+		}
+		d += 1;
+	}
+	true
+}
+
+#[test]
+fn is_prime_matches_trial_division() {
+	for n in (0..100_000).chain(1_000_000_000..1_000_002_000).chain(4_294_967_000..4_294_968_000) {
+		assert_eq!(is_prime(n), trial_division_is_prime(n), "{}", n);
+	}
+}
+
+#[test]
+fn is_prime_large() {
+	assert!(is_prime(18446744073709551557));
+	assert!(is_prime(18446744073709551533));
+	assert!(is_prime((1 << 127) - 1));
+	assert!(is_prime(340282366920938463463374607431768211297));
+	assert!(!is_prime(u128::MAX));
+	assert!(!is_prime(18446744073709551557 * 18446744073709551533));
+}
+
+#[test]
+fn is_prime_strong_pseudoprimes() {
+	//smallest strong pseudoprimes to the prime bases up to 7, 23, 37 and 41
+	assert!(!is_prime(3215031751));
+	assert!(!is_prime(3825123056546413051));
+	assert!(!is_prime(318665857834031151167461));
+	assert!(!is_prime(3317044064679887385961981));
+}
+
+#[test]
+fn factorize_small() {
+	assert_eq!(factorize(0), Vec::<u128>::new());
+	assert_eq!(factorize(1), Vec::<u128>::new());
+	for n in 2..10_000u128 {
+		let factors = factorize(n);
+		assert_eq!(factors.iter().product::<u128>(), n, "{}", n);
+		assert!(factors.iter().all(|p| trial_division_is_prime(*p)), "{}", n);
+		assert!(factors.is_sorted(), "{}", n);
+	}
+}
+
+#[test]
+fn factorize_powers() {
+	let primes = [101, 65537, 4294967291, 18446744073709551557];
+	for p in primes {
+		assert_eq!(factorize(p * p), vec![p, p]);
+	}
+	for p in &primes[..3] {
+		assert_eq!(factorize(p * p * p), vec![*p; 3]);
+	}
+	assert_eq!(factorize(1000003u128.pow(6)), vec![1000003; 6]);
+	assert_eq!(factorize(101u128.pow(19)), vec![101; 19]);
+	assert_eq!(factorize(4 * 4294967291 * 4294967291), vec![2, 2, 4294967291, 4294967291]);
+}
+
+#[test]
+fn factorize_u128_max() {
+	assert_eq!(factorize(u128::MAX), vec![3, 5, 17, 257, 641, 65537, 274177, 6700417, 67280421310721]);
+	assert_eq!(factorize(1 << 127), vec![2; 127]);
+	assert_eq!(largest_prime_factor(u128::MAX), 67280421310721);
+}
+
+#[test]
+fn factorize_gives_up_on_two_large_primes() {
+	let n = 18446744073709551557 * 18446744073709551533;
+	assert_eq!(factorize(n), vec![n]);
+	assert!(!PrimeFactors::init(n).is_complete());
+	assert!(PrimeFactors::init(u128::MAX).is_complete());
+}
@@ -0,0 +1,21 @@
+//note names and the nearest MIDI note of a frequency
+
+use interval_info::*;
+
+#[test]
+fn nearest_midi_note() {
+	let a4 = "A4".parse::<Frequency>().unwrap().nearest_midi_note().unwrap();
+	assert_eq!((a4.note, a4.name()), (69, "A4".to_string()));
+	let e5 = Frequency(660.0).nearest_midi_note().unwrap();
+	assert_eq!(e5.note, 76);
+	assert!((e5.offset - 1.955).abs() < 0.001);
+}
+
+#[test]
+fn nearest_midi_note_out_of_range() {
+	assert!(Frequency(f64::INFINITY).nearest_midi_note().is_none());
+	assert!(Frequency(0.0).nearest_midi_note().is_none());
+	assert!(Frequency(440.0).above(2000000.0).nearest_midi_note().is_none());
+	let report = FrequencyReport::new(Frequency(440.0), 2000000.0, 2.0);
+	assert!(report.midi.is_none() && report.pitch_bend.is_none());
+}
@@ -0,0 +1,35 @@
+//interval arithmetic near the limits of u128
+
+use interval_info::*;
+
+#[test]
+fn normalize() {
+	assert_eq!(Interval::new(3,1).normalize(), Interval::new(3,2));
+	assert_eq!(Interval::new(2,3).normalize(), Interval::new(4,3));
+	assert_eq!(Interval::new(2,1).normalize(), Interval::new(1,1));
+	assert_eq!(Interval::new(3,1).normalize_to(Interval::new(3,1)), Interval::new(1,1));
+}
+
+#[test]
+fn normalize_large() {
+	assert_eq!(Interval::new(u128::MAX,1).normalize(), Interval::new(u128::MAX, 1 << 127));
+	assert_eq!(Interval::new(1 << 127,1).normalize(), Interval::new(1,1));
+	assert_eq!(Interval::new(1,1 << 127).normalize(), Interval::new(1,1));
+	assert_eq!(Interval::new(3,u128::MAX).normalize(), Interval::new(1 << 127, u128::MAX / 3));
+	assert_eq!(Interval::new(u128::MAX,3).normalize_to(Interval::new(3,1)), Interval::new(u128::MAX / 3, 3u128.pow(79)));
+	//2^128/(2^128-1) does not fit
+	assert_eq!(Interval::new(1,u128::MAX).checked_normalize(), None);
+}
+
+#[test]
+fn complexity() {
+	assert_eq!(Interval::new(3,2).wilson_complexity(), Some(5));
+	assert_eq!(Interval::new(3,2).gradus_suavitatis(), Some(4));
+	assert_eq!(Interval::new(1,1).wilson_complexity(), Some(0));
+	assert_eq!(Interval::new(1,1).gradus_suavitatis(), Some(1));
+	//two primes just below 2^128
+	let large = Interval::new(340282366920938463463374607431768211297, 340282366920938463463374607431768211283);
+	assert_eq!(large.wilson_complexity(), None);
+	assert_eq!(large.gradus_suavitatis(), None);
+	assert_eq!(large.benedetti_height(), None);
+}
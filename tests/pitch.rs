@@ -0,0 +1,108 @@
+//parsing every notation of an interval
+
+use interval_info::*;
+
+fn ratio(s: &str) -> Interval {
+	match s.parse::<Pitch>() {
+		Ok(Pitch::Ratio(i)) => i,
+		Ok(Pitch::Cents(c)) => panic!("{} parsed to {}c instead of a ratio", s, c),
+		Err(e) => panic!("{} did not parse: {}", s, e),
+	}
+}
+
+fn cents(s: &str) -> f64 {
+	match s.parse::<Pitch>() {
+		Ok(Pitch::Cents(c)) => c,
+		Ok(Pitch::Ratio(i)) => panic!("{} parsed to {} instead of cents", s, i),
+		Err(e) => panic!("{} did not parse: {}", s, e),
+	}
+}
+
+fn error(s: &str) -> IntervalParseError {
+	match s.parse::<Pitch>() {
+		Ok(p) => panic!("{} parsed to {}c", s, p.cents()),
+		Err(e) => e,
+	}
+}
+
+fn assert_cents(s: &str, expected: f64) {
+	let c = cents(s);
+	assert!((c - expected).abs() < 1e-9, "{} parsed to {}c, expected {}c", s, c, expected);
+}
+
+#[test]
+fn ratios() {
+	assert_eq!(ratio("3/2"), Interval::new(3,2));
+	assert_eq!(ratio(" 6/4 "), Interval::new(6,4));
+	assert_eq!(ratio("3"), Interval::new(3,1));
+	assert_eq!(ratio("1.5"), Interval::new(15,10));
+	assert_eq!(ratio("1.25"), Interval::new(125,100));
+	assert_eq!(ratio("2."), Interval::new(2,1));
+}
+
+#[test]
+fn cents_notation() {
+	assert_cents("701.955c", 701.955);
+	assert_cents("-500c", -500.0);
+	assert_cents("1e3c", 1000.0);
+	assert_cents("153600c", MAX_CENTS);
+}
+
+#[test]
+fn monzos() {
+	assert_eq!(ratio("[-1 1>"), Interval::new(3,2));
+	assert_eq!(ratio("[-1 1⟩"), Interval::new(3,2));
+	assert_eq!(ratio("|-4 4 -1>"), Interval::new(81,80));
+	//too large for u128, but still in range as cents
+	assert_cents("[-200 127>", -200.0 * 1200.0 + 127.0 * 1901.9550008653875);
+}
+
+#[test]
+fn edo_steps() {
+	assert_cents("7\\12", 700.0);
+	assert_cents("-7\\12", -700.0);
+	assert_cents("7\\13<3>", 1901.9550008653875 * 7.0 / 13.0);
+	assert_cents("1\\2<3/2>", 701.9550008653874 / 2.0);
+}
+
+#[test]
+fn powers() {
+	assert_cents("3^(1/9)", 1901.9550008653875 / 9.0);
+	assert_cents("(3/2)^(1/2)", 701.9550008653874 / 2.0);
+	assert_cents("2^0.5", 600.0);
+	assert_eq!(ratio("(3/2)^2"), Interval::new(9,4));
+	assert_eq!(ratio("3^-1"), Interval::new(1,3));
+	assert_eq!(ratio("(3/2)^-2"), Interval::new(4,9));
+	assert_eq!(ratio("3^80"), Interval::new(3u128.pow(80),1));
+}
+
+#[test]
+fn errors() {
+	assert!(matches!(error(""), IntervalParseError::Empty));
+	assert!(matches!(error("   "), IntervalParseError::Empty));
+	assert!(matches!(error("fifth"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("3/x"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("1.2.3"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("-1.5"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("xc"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("x\\12"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("7\\13<3"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("3^x"), IntervalParseError::NonNumeric(_)));
+	assert!(matches!(error("0/1"), IntervalParseError::Zero));
+	assert!(matches!(error("3/0"), IntervalParseError::Zero));
+	assert!(matches!(error("0.0"), IntervalParseError::Zero));
+	assert!(matches!(error("7\\0"), IntervalParseError::Zero));
+	assert!(matches!(error("340282366920938463463374607431768211456/1"), IntervalParseError::Overflow));
+	assert!(matches!(error("1.0000000000000000000000000000000000000001"), IntervalParseError::Overflow));
+	assert!(matches!(error("[1 x>"), IntervalParseError::Monzo(MonzoParseError::InvalidExponent(_))));
+	assert!(matches!(error("[1 1"), IntervalParseError::Monzo(MonzoParseError::MissingBrackets)));
+	assert!(matches!(error("infc"), IntervalParseError::NotFinite));
+	assert!(matches!(error("3^(1/0)"), IntervalParseError::NotFinite));
+	assert!(matches!(error("1e300c"), IntervalParseError::OutOfRange));
+	assert!(matches!(error("-2000000c"), IntervalParseError::OutOfRange));
+	assert!(matches!(error("1000000\\1"), IntervalParseError::OutOfRange));
+	assert!(matches!(error("3^81"), IntervalParseError::OutOfRange));
+	assert!(matches!(error("3^1000"), IntervalParseError::OutOfRange));
+	assert!(matches!(error("[0 1000>"), IntervalParseError::OutOfRange));
+	assert!(matches!("3".parse::<Interval>(), Err(IntervalParseError::MissingSeparator)));
+}
@@ -0,0 +1,83 @@
+//reading Scala .scl files
+
+use interval_info::*;
+
+fn parse(s: &str) -> ScalaFile {
+	ScalaFile::parse(s).unwrap()
+}
+
+fn assert_steps(scale: &Scale, steps: &[f64]) {
+	assert_eq!(scale.len(), steps.len());
+	for (a, b) in scale.steps.iter().zip(steps) {
+		assert!((a - b).abs() < 1e-9, "{:?} != {:?}", scale.steps, steps);
+	}
+}
+
+#[test]
+fn cents_and_ratio_lines() {
+	let scl = parse("! test.scl\n!\nmixed\n 3\n!\n 386.3137\n 3/2\n 2/1\n");
+	assert_eq!(scl.description, "mixed");
+	assert_steps(&scl.scale, &[0.0, 386.3137, 701.955000865387]);
+	assert_eq!(scl.scale.period, 1200.0);
+	assert_eq!(scl.scale.ratio(1), None);
+	assert_eq!(scl.scale.ratio(2), Some(&Interval::new(3,2)));
+	assert_eq!(scl.scale.ratio(3), Some(&Interval::new(2,1)));
+}
+
+#[test]
+fn bare_integers_are_ratios() {
+	let scl = parse("harmonics\n3\n5/4\n3\n4\n");
+	assert_eq!(scl.scale.ratio(3), Some(&Interval::new(4,1)));
+	assert_steps(&scl.scale, &[0.0, 386.3137138648348, 1901.9550008653875]);
+	//100 is the ratio 100/1, not 100 cents
+	let scl = parse("x\n1\n100\n");
+	assert!((scl.scale.period - 7972.627427729669).abs() < 1e-9);
+}
+
+#[test]
+fn trailing_text_is_ignored() {
+	let scl = parse("x\n 2 notes\n 3/2 fifth\n 1200.0 ! octave\n");
+	assert_steps(&scl.scale, &[0.0, 701.955000865387]);
+	assert_eq!(scl.scale.period, 1200.0);
+}
+
+#[test]
+fn comments_and_blank_lines() {
+	let scl = parse("!comment\n!\nx\n!\n2\n\n! between\n   \n9/8\n\n2/1\n\n");
+	assert_steps(&scl.scale, &[0.0, 203.91000173077484]);
+}
+
+#[test]
+fn blank_description() {
+	let scl = parse("!\n\n 1\n 2/1\n");
+	assert_eq!(scl.description, "");
+	assert_eq!(scl.scale.len(), 1);
+}
+
+#[test]
+fn unordered_and_negative_pitches() {
+	let scl = parse("x\n4\n3/2\n-100.0\n5/4\n2/1\n");
+	assert_steps(&scl.scale, &[0.0, 386.3137138648348, 701.955000865387, 1100.0]);
+	assert_eq!(scl.scale.ratio(1), Some(&Interval::new(5,4)));
+	assert_eq!(scl.scale.ratio(2), Some(&Interval::new(3,2)));
+	assert_eq!(scl.scale.ratio(3), None);
+	//ratios below 1/1 and above the period are moved into it
+	let scl = parse("x\n3\n2/3\n9/4\n2/1\n");
+	assert_eq!(scl.scale.ratio(1), Some(&Interval::new(9,8)));
+	assert_eq!(scl.scale.ratio(2), Some(&Interval::new(4,3)));
+	assert_eq!(scl.scale.closest_note(-700.0, true).unwrap().note, -1);
+}
+
+#[test]
+fn errors() {
+	assert!(matches!(ScalaFile::parse("x\n3\n9/8\n2/1\n"), Err(ScalaError::WrongNoteCount{expected: 3, found: 2})));
+	assert!(matches!(ScalaFile::parse("x\n1\n9/8\n2/1\n"), Err(ScalaError::WrongNoteCount{expected: 1, found: 2})));
+	assert!(matches!(ScalaFile::parse("! only a comment\n"), Err(ScalaError::MissingDescription)));
+	assert!(matches!(ScalaFile::parse("x\n"), Err(ScalaError::MissingNoteCount)));
+	assert!(matches!(ScalaFile::parse("x\nmany\n"), Err(ScalaError::InvalidNoteCount(_))));
+	assert!(matches!(ScalaFile::parse("x\n0\n"), Err(ScalaError::Empty)));
+	assert!(matches!(ScalaFile::parse("x\n1\nfifth\n"), Err(ScalaError::InvalidPitch(_))));
+	assert!(matches!(ScalaFile::parse("x\n1\n0/1\n"), Err(ScalaError::InvalidPitch(_))));
+	assert!(matches!(ScalaFile::parse("x\n2\n9/8\n1/1\n"), Err(ScalaError::InvalidPeriod)));
+	assert!(matches!(ScalaFile::parse("x\n2\n9/8\n-1200.0\n"), Err(ScalaError::InvalidPeriod)));
+}
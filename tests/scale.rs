@@ -0,0 +1,68 @@
+//finding the closest note of a scale
+
+use interval_info::*;
+
+fn closest(scale: &Scale, cents: f64) -> (i64, f64) {
+	let a = scale.closest_note(cents, true).unwrap();
+	(a.note, a.offset)
+}
+
+fn assert_close(scale: &Scale, cents: f64, note: i64, offset: f64) {
+	let (n, o) = closest(scale, cents);
+	assert_eq!(n, note, "note for {}c", cents);
+	assert!((o - offset).abs() < 1e-6, "offset {} for {}c, expected {}", o, cents, offset);
+}
+
+#[test]
+fn closest_note_far_above_the_octave() {
+	let edo = Scale::edo(12);
+	assert_close(&edo, 701.955, 7, -1.955);
+	assert_close(&edo, 12.0 * 1200.0 + 701.955, 151, -1.955);
+	assert_close(&Scale::just_major(), 5.0 * 1200.0 + 390.0, 37, 386.3137138648348 - 390.0);
+}
+
+#[test]
+fn closest_note_negative_cents() {
+	let edo = Scale::edo(12);
+	assert_close(&edo, -500.0, -5, 0.0);
+	assert_close(&edo, -701.955, -7, 1.955);
+	assert_close(&edo, -1210.0, -12, 10.0);
+	assert_close(&edo, -1.0, 0, 1.0);
+}
+
+#[test]
+fn closest_note_ties_go_up() {
+	let edo = Scale::edo(12);
+	assert_close(&edo, 50.0, 1, 50.0);
+	assert_close(&edo, -50.0, 0, 50.0);
+	assert_close(&edo, 1150.0, 12, 50.0);
+}
+
+#[test]
+fn closest_note_tonic_above_zero() {
+	let scale = Scale::new(1200.0, vec![100.0, 700.0]);
+	assert_close(&scale, 0.0, 0, 100.0);
+	assert_close(&scale, -450.0, -1, -50.0);
+	assert_close(&scale, 1250.0, 2, 50.0);
+	assert_close(&scale, 900.0, 1, -200.0);
+	assert_close(&scale, 1000.0, 2, 300.0);
+}
+
+#[test]
+fn closest_note_counted_from_1() {
+	let a = Scale::edo(12).closest_note(700.0, false).unwrap();
+	assert_eq!(a.note, 8);
+}
+
+#[test]
+fn closest_note_errors() {
+	let edo = Scale::edo(12);
+	assert_eq!(edo.closest_note(3e22, true).err(), Some(ScaleError::OutOfRange));
+	assert_eq!(edo.closest_note(-3e22, true).err(), Some(ScaleError::OutOfRange));
+	assert_eq!(edo.closest_note(1e300, true).err(), Some(ScaleError::OutOfRange));
+	assert_eq!(edo.closest_note(f64::INFINITY, true).err(), Some(ScaleError::NotFinite));
+	assert_eq!(edo.closest_note(f64::NAN, true).err(), Some(ScaleError::NotFinite));
+	assert_eq!(Scale::new(1200.0, Vec::new()).closest_note(0.0, true).err(), Some(ScaleError::Empty));
+	assert_eq!(Scale::new(0.0, vec![0.0]).closest_note(0.0, true).err(), Some(ScaleError::InvalidPeriod));
+	assert!(edo.closest_note(1e20, true).is_ok());
+}